@@ -0,0 +1,79 @@
+//! Decoding throughput, in megabytes of input per second, to compare
+//! changes to the buffering of the decoders against. Run with
+//! `cargo bench --bench decode`.
+//!
+//! The streams are decoded from memory, so that only the decoder is measured.
+//! The ones made of the test vectors of minimp3 are skipped when these
+//! aren't checked out.
+//!
+//! To compare a change against a baseline, run the same file on both
+//! revisions, such as in a `git worktree` of the baseline, on the same
+//! machine and one after the other.
+
+use minimp3::{Decoder, Error, PushDecoder};
+use std::{
+    fs,
+    io::Cursor,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+const VECTOR: &str = "minimp3-sys/minimp3/vectors/M2L3_bitrate_24_all.bit";
+
+// Size of the streams decoded, repeating the vector as needed.
+const STREAM_LEN: usize = 4 << 20;
+
+fn bench(name: &str, data: &[u8], mut run: impl FnMut(&[u8])) {
+    run(data);
+
+    let start = Instant::now();
+    let mut iterations = 0;
+    while start.elapsed() < Duration::from_secs(2) {
+        run(data);
+        iterations += 1;
+    }
+
+    let bytes = (data.len() * iterations) as f64;
+    let throughput = bytes / start.elapsed().as_secs_f64() / 1e6;
+    println!("{:<24} {:>10.1} MB/s", name, throughput);
+}
+
+fn decode(data: &[u8]) {
+    let mut decoder = Decoder::new(Cursor::new(data));
+    loop {
+        match decoder.next_frame_ref() {
+            Ok(_) => {}
+            Err(Error::Eof) => break,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+}
+
+// Feeds the stream in small chunks, as packets of a network stream.
+fn push(data: &[u8]) {
+    let mut decoder = PushDecoder::new();
+    for chunk in data.chunks(1500) {
+        decoder.feed(chunk);
+        while decoder.poll_frame().is_some() {}
+    }
+
+    decoder.finish();
+    while decoder.poll_frame().is_some() {}
+}
+
+fn main() {
+    // Nothing but garbage, which is skipped as fast as it can be buffered.
+    let garbage = vec![0; STREAM_LEN];
+    bench("decoder/garbage", &garbage, decode);
+    bench("push/garbage", &garbage, push);
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(VECTOR);
+    match fs::read(&path) {
+        Ok(vector) => {
+            let stream = vector.repeat(STREAM_LEN / vector.len() + 1);
+            bench("decoder/vector", &stream, decode);
+            bench("push/vector", &stream, push);
+        }
+        Err(_) => println!("{} not found, skipping", path.display()),
+    }
+}
@@ -0,0 +1,27 @@
+//! This example must be run with the "async_futures" feature flag:
+//!
+//! ```bash
+//! $ cargo run --example example_futures --features async_futures
+//! ```
+use futures::{executor, io::AllowStdIo};
+use minimp3::{Decoder, Error, Frame};
+
+use std::fs::File;
+
+fn main() {
+    executor::block_on(async {
+        let mut decoder = Decoder::new(AllowStdIo::new(
+            File::open("minimp3-sys/minimp3/vectors/M2L3_bitrate_24_all.bit").unwrap(),
+        ));
+
+        loop {
+            match decoder.next_frame_future().await {
+                Ok(Frame { data, channels, .. }) => {
+                    println!("Decoded {} samples", data.len() / channels)
+                }
+                Err(Error::Eof) => break,
+                Err(e) => panic!("{:?}", e),
+            }
+        }
+    });
+}
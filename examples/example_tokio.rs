@@ -3,7 +3,8 @@
 //! ```bash
 //! $ cargo run --example example_tokio --features async_tokio
 //! ```
-use minimp3::{Decoder, Error, Frame};
+use futures::StreamExt;
+use minimp3::{Decoder, Frame};
 
 use tokio::fs::File;
 
@@ -15,16 +16,9 @@ async fn main() {
             .unwrap(),
     );
 
-    loop {
-        match decoder.next_frame_future().await {
-            Ok(Frame {
-                data,
-                sample_rate,
-                channels,
-                ..
-            }) => println!("Decoded {} samples", data.len() / channels),
-            Err(Error::Eof) => break,
-            Err(e) => panic!("{:?}", e),
-        }
+    let mut frames = decoder.frames();
+    while let Some(frame) = frames.next().await {
+        let Frame { data, channels, .. } = frame.unwrap();
+        println!("Decoded {} samples", data.len() / channels)
     }
 }
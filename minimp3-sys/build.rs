@@ -1,9 +1,79 @@
 extern crate cc;
 
+use std::env;
+
 fn main() {
-    cc::Build::new()
+    let mut build = cc::Build::new();
+    build
         .include("minimp3/")
         .file("minimp3.c")
         .define("MINIMP3_IMPLEMENTATION", None)
-        .compile("minimp3");
+        // Leave out the file and mmap based functions of minimp3_ex, which
+        // aren't bound.
+        .define("MINIMP3_NO_STDIO", None);
+
+    let options = [
+        (cfg!(feature = "float_output"), "MINIMP3_FLOAT_OUTPUT"),
+        (cfg!(feature = "only_mp3"), "MINIMP3_ONLY_MP3"),
+        (cfg!(feature = "only_simd"), "MINIMP3_ONLY_SIMD"),
+        (cfg!(feature = "no_simd"), "MINIMP3_NO_SIMD"),
+        (
+            cfg!(feature = "nonstandard_but_logical"),
+            "MINIMP3_NONSTANDARD_BUT_LOGICAL",
+        ),
+    ];
+    for &(enabled, define) in options.iter() {
+        if enabled {
+            build.define(define, None);
+        }
+    }
+
+    // Bare metal targets and wasm32-unknown-unknown have no C library: the
+    // few declarations minimp3 needs come from `freestanding/`, and the
+    // functions themselves from Rust's compiler_builtins. minimp3_ex, which
+    // needs malloc, is left out.
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    if target_os == "none" || target_os == "unknown" {
+        build
+            .include("freestanding/")
+            .flag("-ffreestanding")
+            .define("MINIMP3_SYS_FREESTANDING", None);
+    }
+
+    build.compile("minimp3");
+
+    #[cfg(feature = "bindgen")]
+    generate_bindings();
+}
+
+// Generates the bindings into `$OUT_DIR/bindings.rs`, with the same options as
+// the checked-in ones (see the README).
+#[cfg(feature = "bindgen")]
+fn generate_bindings() {
+    let mut builder = bindgen::Builder::default()
+        .header("minimp3.c")
+        .clang_arg("-Iminimp3")
+        .clang_arg("-DMINIMP3_NO_STDIO")
+        .allowlist_function("mp3dec_.*")
+        .allowlist_type("mp3dec_.*")
+        // The `MP3D_*` constants of minimp3_ex (seek modes and error codes)
+        // are needed as well.
+        .allowlist_var("MINIMP3_.*|MP3D_.*")
+        .use_core()
+        .ctypes_prefix("::core::ffi")
+        // bindgen's layout tests hardcode the sizes of the host they were
+        // generated on, tests/layout.rs checks them on the target instead.
+        .layout_tests(false)
+        .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()));
+
+    if cfg!(feature = "float_output") {
+        builder = builder.clang_arg("-DMINIMP3_FLOAT_OUTPUT");
+    }
+
+    let out = std::path::PathBuf::from(env::var("OUT_DIR").unwrap());
+    builder
+        .generate()
+        .expect("Unable to generate bindings")
+        .write_to_file(out.join("bindings.rs"))
+        .expect("Unable to write bindings");
 }
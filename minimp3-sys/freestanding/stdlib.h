@@ -0,0 +1,6 @@
+/* An empty <stdlib.h>, for the include of minimp3 on targets without a C
+ * library. */
+#ifndef MINIMP3_SYS_STDLIB_H
+#define MINIMP3_SYS_STDLIB_H
+
+#endif
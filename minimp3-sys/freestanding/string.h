@@ -0,0 +1,13 @@
+/* The part of <string.h> used by minimp3, for targets without a C library.
+ * The functions are provided by Rust's compiler_builtins. */
+#ifndef MINIMP3_SYS_STRING_H
+#define MINIMP3_SYS_STRING_H
+
+#include <stddef.h>
+
+void *memcpy(void *dest, const void *src, size_t n);
+void *memmove(void *dest, const void *src, size_t n);
+void *memset(void *s, int c, size_t n);
+int memcmp(const void *s1, const void *s2, size_t n);
+
+#endif
@@ -1 +1,5 @@
 #include <minimp3.h>
+/* minimp3_ex allocates with malloc, so it is only built with a C library. */
+#ifndef MINIMP3_SYS_FREESTANDING
+#include <minimp3_ex.h>
+#endif
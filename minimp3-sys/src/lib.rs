@@ -1,3 +1,13 @@
+#![no_std]
 #![allow(bad_style)]
 
+#[cfg(feature = "bindgen")]
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+#[cfg(all(not(feature = "bindgen"), not(feature = "float_output")))]
 include!("bindings.rs");
+
+// Generated with `-DMINIMP3_FLOAT_OUTPUT`, which switches `mp3d_sample_t` to
+// `f32` and declares `mp3dec_f32_to_s16`.
+#[cfg(all(not(feature = "bindgen"), feature = "float_output"))]
+include!("bindings_float.rs");
@@ -0,0 +1,84 @@
+//! Checks that the checked-in bindings still match the headers, by comparing
+//! them with the bindings generated by the `bindgen` feature.
+#![cfg(feature = "bindgen")]
+#![allow(bad_style, dead_code)]
+
+// The bindings refer to `::core`, which is only in scope at the crate root
+// in the 2015 edition.
+extern crate core;
+
+use minimp3_sys as generated;
+use std::mem::{align_of, offset_of, size_of};
+
+mod checked_in {
+    #[cfg(not(feature = "float_output"))]
+    include!("../src/bindings.rs");
+
+    #[cfg(feature = "float_output")]
+    include!("../src/bindings_float.rs");
+}
+
+macro_rules! assert_same_layout {
+    ($($ty:ident { $($field:ident),* $(,)? })*) => {$(
+        assert_eq!(
+            size_of::<checked_in::$ty>(),
+            size_of::<generated::$ty>(),
+            concat!("Size of ", stringify!($ty)),
+        );
+        assert_eq!(
+            align_of::<checked_in::$ty>(),
+            align_of::<generated::$ty>(),
+            concat!("Alignment of ", stringify!($ty)),
+        );
+        $(
+            assert_eq!(
+                offset_of!(checked_in::$ty, $field),
+                offset_of!(generated::$ty, $field),
+                concat!("Offset of ", stringify!($ty), "::", stringify!($field)),
+            );
+        )*
+    )*};
+}
+
+macro_rules! assert_same_value {
+    ($($name:ident),* $(,)?) => {$(
+        assert_eq!(checked_in::$name, generated::$name, stringify!($name));
+    )*};
+}
+
+#[test]
+fn checked_in_bindings_match_headers() {
+    assert_same_layout! {
+        mp3dec_frame_info_t { frame_bytes, frame_offset, channels, hz, layer, bitrate_kbps }
+        mp3dec_t { mdct_overlap, qmf_state, reserv, free_format_bytes, header, reserv_buf }
+        mp3dec_file_info_t { buffer, samples, channels, hz, layer, avg_bitrate_kbps }
+        mp3dec_map_info_t { buffer, size }
+        mp3dec_frame_t { sample, offset }
+        mp3dec_index_t { frames, num_frames, capacity }
+        mp3dec_io_t { read, read_data, seek, seek_data }
+        mp3dec_ex_t {
+            mp3d, file, io, index, offset, samples, detected_samples, cur_sample,
+            start_offset, end_offset, info, buffer, input_consumed, input_filled,
+            is_file, flags, vbr_tag_found, indexes_built, free_format_bytes,
+            buffer_samples, buffer_consumed, to_skip, start_delay, last_error,
+        }
+    }
+
+    assert_same_value! {
+        MINIMP3_MAX_SAMPLES_PER_FRAME,
+        MINIMP3_BUF_SIZE,
+        MP3D_SEEK_TO_BYTE,
+        MP3D_SEEK_TO_SAMPLE,
+        MP3D_E_PARAM,
+        MP3D_E_MEMORY,
+        MP3D_E_IOERROR,
+        MP3D_E_USER,
+        MP3D_E_DECODE,
+    }
+
+    assert_eq!(
+        size_of::<checked_in::mp3d_sample_t>(),
+        size_of::<generated::mp3d_sample_t>(),
+        "Size of mp3d_sample_t",
+    );
+}
@@ -0,0 +1,206 @@
+use alloc::{string::String, vec::Vec};
+
+pub(crate) const FOOTER_LEN: usize = 32;
+
+const FLAG_HAS_HEADER: u32 = 1 << 31;
+
+/// An APEv2 tag found at the end of a stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApeTag {
+    /// Version of the tag, 2000 for APEv2 or 1000 for APEv1.
+    pub version: u32,
+    /// The items of the tag, in the order they appear.
+    pub items: Vec<ApeItem>,
+}
+
+/// An item of an APEv2 tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApeItem {
+    /// Key of the item, such as `Title` or `Artist`. Keys are case insensitive.
+    pub key: String,
+    /// Value of the item.
+    pub value: ApeValue,
+}
+
+/// The value of an APEv2 item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApeValue {
+    /// UTF-8 text. Multiple values are separated by `\0`.
+    Text(String),
+    /// Binary data, such as cover art.
+    Binary(Vec<u8>),
+    /// A link to an external resource.
+    Locator(String),
+}
+
+impl ApeTag {
+    /// Return the text value of the first item with the given key, compared
+    /// case insensitively.
+    pub fn text(&self, key: &str) -> Option<&str> {
+        self.items.iter().find_map(|item| match &item.value {
+            ApeValue::Text(text) if item.key.eq_ignore_ascii_case(key) => Some(text.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// The footer of an APEv2 tag.
+pub(crate) struct Footer {
+    version: u32,
+    // Size of the items and the footer.
+    size: u32,
+    items: usize,
+    has_header: bool,
+}
+
+impl Footer {
+    /// Parses the footer held in `data`, if `data` holds one.
+    pub(crate) fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() != FOOTER_LEN || !data.starts_with(b"APETAGEX") {
+            return None;
+        }
+
+        let footer = Self {
+            version: le(&data[8..12]),
+            size: le(&data[12..16]),
+            items: le(&data[16..20]) as usize,
+            has_header: le(&data[20..24]) & FLAG_HAS_HEADER != 0,
+        };
+        if (footer.size as usize) < FOOTER_LEN {
+            return None;
+        }
+
+        Some(footer)
+    }
+
+    /// Size of the whole tag, including its header and footer. It is read
+    /// from the tag, so it has to be checked against the size of the stream.
+    pub(crate) fn tag_len(&self) -> u64 {
+        u64::from(self.size)
+            + if self.has_header {
+                FOOTER_LEN as u64
+            } else {
+                0
+            }
+    }
+
+    /// Size of the items of the tag, which precede the footer.
+    pub(crate) fn items_len(&self) -> usize {
+        self.size as usize - FOOTER_LEN
+    }
+
+    /// Parses the items held in `data`, as delimited by
+    /// [`items_len`](Footer::items_len).
+    pub(crate) fn parse_items(&self, mut data: &[u8]) -> ApeTag {
+        let mut tag = ApeTag {
+            version: self.version,
+            items: Vec::with_capacity(self.items.min(64)),
+        };
+
+        for _ in 0..self.items {
+            if data.len() < 8 {
+                break;
+            }
+            let len = le(&data[..4]) as usize;
+            let flags = le(&data[4..8]);
+            data = &data[8..];
+
+            let key_len = match data.iter().position(|&b| b == 0) {
+                Some(key_len) => key_len,
+                None => break,
+            };
+            let key = String::from_utf8_lossy(&data[..key_len]).into_owned();
+            data = &data[key_len + 1..];
+
+            let value = match data.get(..len) {
+                Some(value) => value,
+                None => break,
+            };
+            data = &data[len..];
+
+            let value = match (flags >> 1) & 0x3 {
+                1 => ApeValue::Binary(value.to_vec()),
+                2 => ApeValue::Locator(String::from_utf8_lossy(value).into_owned()),
+                _ => ApeValue::Text(String::from_utf8_lossy(value).into_owned()),
+            };
+            tag.items.push(ApeItem { key, value });
+        }
+
+        tag
+    }
+}
+
+fn le(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn footer_bytes(size: u32, items: u32, flags: u32) -> Vec<u8> {
+        let mut data = b"APETAGEX".to_vec();
+        for field in [2000, size, items, flags] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(&[0; 8]);
+        data
+    }
+
+    fn item(key: &str, flags: u32, value: &[u8]) -> Vec<u8> {
+        let mut data = (value.len() as u32).to_le_bytes().to_vec();
+        data.extend_from_slice(&flags.to_le_bytes());
+        data.extend_from_slice(key.as_bytes());
+        data.push(0);
+        data.extend_from_slice(value);
+        data
+    }
+
+    #[test]
+    fn footer_len() {
+        let footer = Footer::parse(&footer_bytes(132, 0, 0)).unwrap();
+        assert_eq!(footer.tag_len(), 132);
+        assert_eq!(footer.items_len(), 100);
+
+        let footer = Footer::parse(&footer_bytes(132, 0, FLAG_HAS_HEADER)).unwrap();
+        assert_eq!(footer.tag_len(), 164);
+        assert_eq!(footer.items_len(), 100);
+
+        // Sizes don't overflow on 32-bit targets.
+        let footer = Footer::parse(&footer_bytes(u32::MAX, 0, FLAG_HAS_HEADER)).unwrap();
+        assert_eq!(footer.tag_len(), (1 << 32) + 31);
+    }
+
+    #[test]
+    fn invalid_footer() {
+        assert!(Footer::parse(&footer_bytes(31, 0, 0)).is_none());
+        assert!(Footer::parse(&footer_bytes(32, 0, 0)[..31]).is_none());
+
+        let mut data = footer_bytes(32, 0, 0);
+        data[0] = b'a';
+        assert!(Footer::parse(&data).is_none());
+    }
+
+    #[test]
+    fn items() {
+        let mut items = item("Title", 0, b"Title");
+        items.extend(item("Cover Art (Front)", 1 << 1, &[0xff, 0xd8]));
+        items.extend(item("Related", 2 << 1, b"http://example.com"));
+        // Truncated item
+        items.extend(item("Artist", 0, b"Artist"));
+        items.truncate(items.len() - 1);
+
+        let footer = Footer::parse(&footer_bytes(items.len() as u32 + 32, 4, 0)).unwrap();
+        let tag = footer.parse_items(&items);
+        assert_eq!(tag.version, 2000);
+        assert_eq!(tag.items.len(), 3);
+        assert_eq!(tag.text("TITLE"), Some("Title"));
+        assert_eq!(tag.text("Artist"), None);
+        assert_eq!(tag.items[1].value, ApeValue::Binary(vec![0xff, 0xd8]));
+        assert_eq!(
+            tag.items[2].value,
+            ApeValue::Locator("http://example.com".into())
+        );
+    }
+}
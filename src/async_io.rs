@@ -0,0 +1,110 @@
+use crate::{pcm_array, Decoder, Error, Frame, FrameInfo, Sample, MAX_SAMPLES_PER_FRAME};
+use std::{
+    io, mem,
+    task::{ready, Context, Poll},
+};
+
+/// An asynchronous reader that a [`Decoder`] can decode from: a
+/// `tokio::io::AsyncRead` with the `async_tokio` feature, or a
+/// `futures_io::AsyncRead` with the `async_futures` feature.
+///
+/// `M` is either [`TokioIo`] or [`FuturesIo`], telling which of the two traits
+/// is used. It is inferred, unless the reader implements both, in which case
+/// it has to be given, as in `decoder.next_frame_future::<TokioIo>()`.
+pub trait AsyncSource<M>: Unpin {
+    /// Attempts to read into `buf`, returning how many bytes were read.
+    fn poll_read_into(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>>;
+}
+
+/// Marks readers used through `tokio::io::AsyncRead`. See [`AsyncSource`].
+#[cfg(feature = "async_tokio")]
+#[derive(Debug)]
+pub enum TokioIo {}
+
+/// Marks readers used through `futures_io::AsyncRead`. See [`AsyncSource`].
+#[cfg(feature = "async_futures")]
+#[derive(Debug)]
+pub enum FuturesIo {}
+
+#[cfg(feature = "async_tokio")]
+impl<R: tokio::io::AsyncRead + Unpin> AsyncSource<TokioIo> for R {
+    fn poll_read_into(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let mut buf = tokio::io::ReadBuf::new(buf);
+        ready!(std::pin::Pin::new(self).poll_read(cx, &mut buf))?;
+
+        Poll::Ready(Ok(buf.filled().len()))
+    }
+}
+
+#[cfg(feature = "async_futures")]
+impl<R: futures_io::AsyncRead + Unpin> AsyncSource<FuturesIo> for R {
+    fn poll_read_into(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        std::pin::Pin::new(self).poll_read(cx, buf)
+    }
+}
+
+impl<R> Decoder<R> {
+    /// Reads a new frame from the internal reader. Returns a [`Frame`](Frame)
+    /// if one was found, or, otherwise, an `Err` explaining why not.
+    pub async fn next_frame_future<M>(&mut self) -> Result<Frame, Error>
+    where
+        R: AsyncSource<M>,
+    {
+        std::future::poll_fn(|cx| self.poll_next_frame::<M>(cx)).await
+    }
+
+    /// Attempts to read a new frame from the internal reader, registering the
+    /// current task for wakeup if the reader isn't ready yet.
+    pub fn poll_next_frame<M>(&mut self, cx: &mut Context<'_>) -> Poll<Result<Frame, Error>>
+    where
+        R: AsyncSource<M>,
+    {
+        let mut pcm = mem::take(&mut self.pcm);
+        let result = self.poll_next_frame_into::<M>(cx, pcm_array(&mut pcm));
+        self.pcm = pcm;
+
+        result.map_ok(|info| Frame::new(self.pcm[..info.samples * info.channels].to_vec(), info))
+    }
+
+    /// Attempts to read a new frame from the internal reader into `pcm`,
+    /// without allocating. See [`next_frame_into`](Decoder::next_frame_into).
+    pub fn poll_next_frame_into<M>(
+        &mut self,
+        cx: &mut Context<'_>,
+        pcm: &mut [Sample; MAX_SAMPLES_PER_FRAME],
+    ) -> Poll<Result<FrameInfo, Error>>
+    where
+        R: AsyncSource<M>,
+    {
+        loop {
+            match self.core.decode_into(pcm) {
+                Err(Error::InsufficientData) => ready!(self.poll_refill::<M>(cx))?,
+                result => return Poll::Ready(result),
+            }
+        }
+    }
+
+    fn poll_refill<M>(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), io::Error>>
+    where
+        R: AsyncSource<M>,
+    {
+        let len = self.core.input_len(self.buffer_refill.len());
+        let read_bytes = loop {
+            match ready!(self
+                .reader
+                .poll_read_into(cx, &mut self.buffer_refill[..len]))
+            {
+                Ok(read_bytes) => break read_bytes,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        };
+        if read_bytes == 0 {
+            self.core.finish();
+        } else {
+            self.core.push(&self.buffer_refill[..read_bytes]);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
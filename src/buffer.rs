@@ -0,0 +1,173 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+// A FIFO of bytes which are always contiguous in memory, as minimp3 needs
+// them: data is consumed from the front by moving a start index, and the
+// consumed bytes are only dropped, moving the rest to the front of the
+// vector, when the space they take is needed.
+#[derive(Debug, Default)]
+pub(crate) struct Buffer {
+    data: Vec<u8>,
+    // Index in `data` of the first byte not consumed yet.
+    start: usize,
+}
+
+impl Buffer {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity),
+            start: 0,
+        }
+    }
+
+    // Appends `bytes` at the back.
+    pub(crate) fn extend_from_slice(&mut self, bytes: &[u8]) {
+        // Reuse the space of the consumed bytes rather than growing, as long
+        // as they take enough of it for the copy to pay off.
+        if self.data.len() + bytes.len() > self.data.capacity() && self.start >= self.len() {
+            self.compact();
+        }
+        self.data.extend_from_slice(bytes);
+    }
+
+    // Removes `len` bytes from the front.
+    pub(crate) fn consume(&mut self, len: usize) {
+        assert!(len <= self.len());
+        self.start += len;
+        if self.start == self.data.len() {
+            self.clear();
+        }
+    }
+
+    // Keeps only the first `len` bytes.
+    #[cfg(feature = "std")]
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.data.truncate(self.start + len);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.data.clear();
+        self.start = 0;
+    }
+
+    fn compact(&mut self) {
+        self.data.drain(..self.start);
+        self.start = 0;
+    }
+}
+
+impl Deref for Buffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.data[self.start..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(capacity: usize, bytes: &[u8]) -> Buffer {
+        let mut buffer = Buffer::with_capacity(capacity);
+        buffer.extend_from_slice(bytes);
+        buffer
+    }
+
+    #[test]
+    fn consume() {
+        let mut buffer = buffer(8, &[1, 2, 3, 4]);
+        buffer.consume(1);
+        assert_eq!(&buffer[..], [2, 3, 4]);
+        buffer.consume(0);
+        assert_eq!(&buffer[..], [2, 3, 4]);
+
+        // Consuming everything starts over at the front of the vector.
+        buffer.consume(3);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.start, 0);
+        assert!(buffer.data.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn consume_past_end() {
+        buffer(8, &[1, 2]).consume(3);
+    }
+
+    #[test]
+    fn compact() {
+        let mut buffer = buffer(8, &[1, 2, 3, 4, 5, 6]);
+        let capacity = buffer.data.capacity();
+        buffer.consume(4);
+
+        // The consumed bytes take more space than the ones left, so they are
+        // dropped instead of growing the vector.
+        buffer.extend_from_slice(&[7, 8, 9, 10]);
+        assert_eq!(&buffer[..], [5, 6, 7, 8, 9, 10]);
+        assert_eq!(buffer.start, 0);
+        assert_eq!(buffer.data.capacity(), capacity);
+    }
+
+    #[test]
+    fn grow_without_compacting() {
+        let mut buffer = buffer(8, &[1, 2, 3, 4, 5, 6]);
+        buffer.consume(2);
+
+        // Fewer bytes were consumed than are left: the vector grows.
+        buffer.extend_from_slice(&[7, 8, 9, 10]);
+        assert_eq!(&buffer[..], [3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(buffer.start, 2);
+    }
+
+    #[test]
+    fn contiguous_across_compaction() {
+        let mut buffer = Buffer::with_capacity(16);
+        let mut expected = Vec::new();
+        let mut next = 0u8;
+
+        for step in 0..200 {
+            let len = step % 7 + 1;
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| {
+                    next = next.wrapping_add(1);
+                    next
+                })
+                .collect();
+            buffer.extend_from_slice(&bytes);
+            expected.extend_from_slice(&bytes);
+
+            let consumed = (step % 5).min(buffer.len());
+            buffer.consume(consumed);
+            expected.drain(..consumed);
+            assert_eq!(&buffer[..], &expected[..]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn truncate() {
+        let mut buffer = buffer(8, &[1, 2, 3, 4, 5]);
+        buffer.consume(2);
+        buffer.truncate(2);
+        assert_eq!(&buffer[..], [3, 4]);
+
+        // Truncating to more than is held keeps everything.
+        buffer.truncate(10);
+        assert_eq!(&buffer[..], [3, 4]);
+
+        buffer.truncate(0);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn clear() {
+        let mut buffer = buffer(8, &[1, 2, 3]);
+        buffer.consume(1);
+        buffer.clear();
+        assert!(buffer.is_empty());
+
+        buffer.extend_from_slice(&[4]);
+        assert_eq!(&buffer[..], [4]);
+    }
+}
@@ -0,0 +1,141 @@
+#[cfg(feature = "std")]
+use crate::Decoder;
+use crate::{decoder_core, DecoderCore, PushDecoder, MAX_SAMPLES_PER_FRAME};
+
+// Smallest look-ahead which lets minimp3 confirm a frame with the header of
+// the next one. With less, every frame would be skipped as garbage.
+const MIN_BUFFERED: usize = MAX_SAMPLES_PER_FRAME * 2;
+
+/// A builder for decoders, to tune how the input is buffered and to set the
+/// decoding options up front.
+///
+/// The defaults are the ones of the `new` constructors of the decoders. The
+/// type of the decoded samples isn't an option: it is chosen at compile time,
+/// with the `float_output` feature.
+///
+/// ```no_run
+/// # use minimp3::DecoderBuilder;
+/// // A live stream fed by packets, decoded with as little latency as
+/// // possible.
+/// let decoder = DecoderBuilder::new().refill_threshold(0).build_push();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecoderBuilder {
+    #[cfg(feature = "std")]
+    read_size: usize,
+    refill_threshold: usize,
+    max_buffered: usize,
+    strict: bool,
+    parse_id3v2: bool,
+    gapless: bool,
+    gapless_override: Option<(u16, u16)>,
+}
+
+impl Default for DecoderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DecoderBuilder {
+    /// Creates a builder with the default options.
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            read_size: MAX_SAMPLES_PER_FRAME * 5,
+            refill_threshold: decoder_core::REFILL_TRIGGER,
+            max_buffered: decoder_core::BUFFER_SIZE,
+            strict: false,
+            parse_id3v2: true,
+            gapless: false,
+            gapless_override: None,
+        }
+    }
+
+    /// Sets the number of bytes asked from the reader on each read, 11520 by
+    /// default.
+    #[cfg(feature = "std")]
+    pub fn read_size(mut self, bytes: usize) -> Self {
+        self.read_size = bytes.max(1);
+        self
+    }
+
+    /// Sets how many bytes are kept buffered ahead of the frame being decoded,
+    /// 18432 by default. Decoders reading from a reader read whenever less is
+    /// buffered, while the others wait for more input before decoding, until
+    /// the end of the stream.
+    ///
+    /// A smaller look-ahead lowers the latency of live streams, at the cost of
+    /// resynchronising less reliably on damaged ones. It is kept at 4608 bytes
+    /// at least, for minimp3 to confirm each frame with the header of the next
+    /// one, and at most at the [`max_buffered`](DecoderBuilder::max_buffered)
+    /// bytes.
+    pub fn refill_threshold(mut self, bytes: usize) -> Self {
+        self.refill_threshold = bytes;
+        self
+    }
+
+    /// Sets the maximum number of bytes buffered from the reader, 34560 by
+    /// default. Reads are shortened not to go past it, but for a whole ID3v2
+    /// tag, which has to be buffered at once. The input pushed to the other
+    /// decoders is always buffered whole.
+    pub fn max_buffered(mut self, bytes: usize) -> Self {
+        self.max_buffered = bytes;
+        self
+    }
+
+    /// Enables or disables strict mode. See
+    /// [`DecoderCore::set_strict`].
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Enables or disables the parsing of the ID3v2 tag. See
+    /// [`DecoderCore::set_parse_id3v2`].
+    pub fn parse_id3v2(mut self, enabled: bool) -> Self {
+        self.parse_id3v2 = enabled;
+        self
+    }
+
+    /// Enables or disables gapless playback. See
+    /// [`DecoderCore::set_gapless`].
+    pub fn gapless(mut self, enabled: bool) -> Self {
+        self.gapless = enabled;
+        self
+    }
+
+    /// Enables gapless playback using the given encoder delay and padding. See
+    /// [`DecoderCore::set_gapless_override`].
+    pub fn gapless_override(mut self, encoder_delay: u16, encoder_padding: u16) -> Self {
+        self.gapless_override = Some((encoder_delay, encoder_padding));
+        self
+    }
+
+    /// Creates a decoder reading from `reader` with these options.
+    #[cfg(feature = "std")]
+    pub fn build<R>(&self, reader: R) -> Decoder<R> {
+        Decoder::with_core(reader, self.build_core(), self.read_size)
+    }
+
+    /// Creates a decoder fed with [`PushDecoder::feed`], with these options.
+    pub fn build_push(&self) -> PushDecoder {
+        PushDecoder::with_core(self.build_core())
+    }
+
+    /// Creates a [`DecoderCore`] with these options.
+    pub fn build_core(&self) -> DecoderCore {
+        let max_buffered = self.max_buffered.max(MIN_BUFFERED);
+        let refill_threshold = self.refill_threshold.max(MIN_BUFFERED).min(max_buffered);
+
+        let mut core = DecoderCore::with_buffer_sizes(refill_threshold, max_buffered);
+        core.set_strict(self.strict);
+        core.set_parse_id3v2(self.parse_id3v2);
+        match self.gapless_override {
+            Some((delay, padding)) => core.set_gapless_override(delay, padding),
+            None => core.set_gapless(self.gapless),
+        }
+
+        core
+    }
+}
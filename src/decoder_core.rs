@@ -0,0 +1,803 @@
+use crate::{
+    buffer::Buffer, ffi, gapless::Gapless, header, id3v2, info, trailers, Error, FrameHeader,
+    FrameInfo, Id3v2Tag, Sample, StreamInfo, MAX_SAMPLES_PER_FRAME,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{convert::TryInto, mem, ops::Range, ptr};
+
+pub(crate) const BUFFER_SIZE: usize = MAX_SAMPLES_PER_FRAME * 15;
+pub(crate) const REFILL_TRIGGER: usize = MAX_SAMPLES_PER_FRAME * 8;
+
+/// Statistics about the data a decoder went through, to tell clean streams from
+/// damaged ones, as returned by the `stats` method of each decoder.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecodeStats {
+    /// Number of bytes skipped because they were neither a frame nor a tag.
+    pub bytes_skipped: u64,
+    /// Number of frames decoded into audio.
+    pub frames_decoded: u64,
+    /// Number of frames found but which produced no audio, such as the
+    /// frames whose bit reservoir was lost to a gap.
+    pub frames_dropped: u64,
+    /// Number of times the decoder lost sync after a frame and found frames
+    /// again further on.
+    pub resyncs: u64,
+    /// Byte ranges of the stream which were skipped, in order.
+    pub gaps: Vec<Range<u64>>,
+}
+
+impl DecodeStats {
+    // Records `len` bytes skipped at the given offset of the stream, as part
+    // of the last gap if they directly follow it.
+    fn skip(&mut self, offset: u64, len: usize) {
+        let end = offset + len as u64;
+        self.bytes_skipped += len as u64;
+
+        match self.gaps.last_mut() {
+            Some(gap) if gap.end == offset => gap.end = end,
+            _ => self.gaps.push(offset..end),
+        }
+    }
+
+    // Records that a frame was found again after a gap.
+    fn resync(&mut self) {
+        if self.frames_decoded + self.frames_dropped > 0 {
+            self.resyncs += 1;
+        }
+    }
+}
+
+/// The state of a MP3 decoder, without any IO: bytes are pushed into it, and
+/// it decodes frames out of them, or asks for more input.
+///
+/// This is what `Decoder` (with the `std` feature) is built on, reading from
+/// its reader whenever the core returns [`Error::InsufficientData`]. It can be
+/// used directly to drive decoding from any other kind of source.
+/// [`DecoderBuilder::build_core`](crate::DecoderBuilder::build_core) creates
+/// one buffering more or less ahead.
+///
+/// ```no_run
+/// # use minimp3::{DecoderCore, Error, Sample, MAX_SAMPLES_PER_FRAME};
+/// # fn next_chunk() -> Option<Vec<u8>> { None }
+/// let mut core = DecoderCore::new();
+/// let mut pcm = [Sample::default(); MAX_SAMPLES_PER_FRAME];
+///
+/// loop {
+///     match core.decode_into(&mut pcm) {
+///         Ok(info) => println!("Decoded {} samples", info.samples),
+///         Err(Error::InsufficientData) => match next_chunk() {
+///             Some(chunk) => core.push(&chunk),
+///             None => core.finish(),
+///         },
+///         Err(Error::Eof) => break,
+///         Err(e) => panic!("{:?}", e),
+///     }
+/// }
+/// ```
+pub struct DecoderCore {
+    pub(crate) buffer: Buffer,
+    decoder: Box<ffi::mp3dec_t>,
+    // Byte offset in the stream of the first byte held in `buffer`.
+    pub(crate) offset: u64,
+    // Whether the end of the input has been reached.
+    finished: bool,
+    // Number of bytes to keep buffered ahead, and at most.
+    refill_trigger: usize,
+    #[cfg(feature = "std")]
+    max_buffered: usize,
+    // Number of samples (per channel) to discard before emitting audio again.
+    pub(crate) skip: usize,
+    // Minimum number of bytes to buffer before decoding, to hold a whole
+    // ID3v2 tag.
+    wanted: usize,
+    pub(crate) id3v2_checked: bool,
+    // Whether the ID3v2 tag is buffered and parsed, or dropped as it comes.
+    parse_id3v2: bool,
+    id3v2: Option<Id3v2Tag>,
+    // Number of bytes of the input still to be pushed which are part of an
+    // ID3v2 tag being dropped.
+    pub(crate) discard: u64,
+    // Whether the first frame has been located and checked for a VBR tag.
+    pub(crate) tag_checked: bool,
+    pub(crate) info: Option<StreamInfo>,
+    pub(crate) gapless: Option<Gapless>,
+    // Byte offset in the stream where the audio ends and trailing tags begin.
+    #[cfg(feature = "std")]
+    pub(crate) end: Option<u64>,
+    // Header of the last frame found, to recognise the following ones when
+    // draining the input.
+    last_header: Option<FrameHeader>,
+    pub(crate) stats: DecodeStats,
+    // The run of garbage skipped since the last frame, which ends once the
+    // next frame, or the end of the input, is found.
+    run: Option<Range<u64>>,
+    // Whether skipped data is returned as `Error::Corrupt`.
+    pub(crate) strict: bool,
+}
+
+impl Default for DecoderCore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DecoderCore {
+    /// Creates a new decoder, with no input yet.
+    pub fn new() -> Self {
+        Self::with_buffer_sizes(REFILL_TRIGGER, BUFFER_SIZE)
+    }
+
+    // Creates a new decoder, buffering `refill_trigger` bytes ahead and at
+    // most `max_buffered` bytes when fed by a reader.
+    pub(crate) fn with_buffer_sizes(refill_trigger: usize, max_buffered: usize) -> Self {
+        let mut minidec = unsafe { Box::new(mem::zeroed()) };
+        unsafe { ffi::mp3dec_init(&mut *minidec) }
+
+        Self {
+            buffer: Buffer::with_capacity(max_buffered),
+            decoder: minidec,
+            offset: 0,
+            finished: false,
+            refill_trigger,
+            #[cfg(feature = "std")]
+            max_buffered,
+            skip: 0,
+            wanted: 0,
+            id3v2_checked: false,
+            parse_id3v2: true,
+            id3v2: None,
+            discard: 0,
+            tag_checked: false,
+            info: None,
+            gapless: None,
+            #[cfg(feature = "std")]
+            end: None,
+            last_header: None,
+            stats: DecodeStats::default(),
+            run: None,
+            strict: false,
+        }
+    }
+
+    /// Appends `data` to the input of the decoder.
+    pub fn push(&mut self, mut data: &[u8]) {
+        if self.discard > 0 {
+            let len = self.discard.min(data.len() as u64) as usize;
+            self.discard -= len as u64;
+            self.offset += len as u64;
+            data = &data[len..];
+        }
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Tells the decoder that no more input will be pushed, so that it decodes
+    /// whatever is left and then returns [`Error::Eof`].
+    ///
+    /// What is left is drained frame by frame: the last frames of the stream
+    /// are decoded even though no header follows them to confirm them, and
+    /// whole frames left behind garbage or a truncated frame are still found.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Whether the decoder wants more input before decoding the next frame.
+    ///
+    /// Enough input is kept buffered to resynchronise on damaged streams, so
+    /// this is true as long as less than a few frames are buffered, until
+    /// [`finish`](DecoderCore::finish) is called.
+    pub fn needs_input(&self) -> bool {
+        !self.finished && self.buffer.len() < self.refill_trigger.max(self.wanted)
+    }
+
+    /// Return the number of bytes of input buffered and not decoded yet.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Return the ID3v2 tag found at the start of the stream, if any.
+    pub fn id3v2(&self) -> Option<&Id3v2Tag> {
+        self.id3v2.as_ref()
+    }
+
+    /// Return the information read from the VBR tag (Xing, Info or VBRI) of
+    /// the stream, if it has one.
+    pub fn stream_info(&self) -> Option<&StreamInfo> {
+        self.info.as_ref()
+    }
+
+    /// Return statistics about the data decoded so far: how many frames were
+    /// decoded or dropped, and how much data was skipped, and where, because
+    /// it was neither a frame nor a tag.
+    pub fn stats(&self) -> &DecodeStats {
+        &self.stats
+    }
+
+    /// Enables or disables the parsing of the ID3v2 tag at the start of the
+    /// stream, enabled by default.
+    ///
+    /// A tag is buffered whole to be parsed, which takes as much memory as
+    /// the tag, cover art included. When disabled, the tag is skipped as it is
+    /// pushed instead, trusting its declared size, and
+    /// [`id3v2`](DecoderCore::id3v2) returns `None`. Must be set before the
+    /// first frame is decoded.
+    pub fn set_parse_id3v2(&mut self, enabled: bool) {
+        self.parse_id3v2 = enabled;
+    }
+
+    /// Enables or disables strict mode, disabled by default.
+    ///
+    /// Data which is neither a frame nor a tag is normally skipped silently
+    /// (and counted in [`stats`](DecoderCore::stats)). In strict mode,
+    /// decoding returns [`Error::Corrupt`] for each run of such data instead,
+    /// once the run ends at the next frame or at the end of the stream, after
+    /// which decoding can go on from that frame.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Decodes the next frame into `pcm`. Returns the [`FrameInfo`] of the
+    /// frame, whose decoded audio is held in the first `samples * channels`
+    /// samples of `pcm`, or, otherwise, an `Err` explaining why not.
+    ///
+    /// Returns [`Error::InsufficientData`] when more input has to be pushed
+    /// (or the input finished) first, and [`Error::Eof`] once the input is
+    /// finished and fully decoded.
+    pub fn decode_into(
+        &mut self,
+        pcm: &mut [Sample; MAX_SAMPLES_PER_FRAME],
+    ) -> Result<FrameInfo, Error> {
+        loop {
+            if self.needs_input() {
+                return Err(Error::InsufficientData);
+            }
+
+            match self.decode_frame_into(pcm) {
+                // Skip garbage until a frame is found, or more input is needed.
+                Err(Error::SkippedData) => {}
+                result => return self.at_end(result),
+            }
+        }
+    }
+
+    // Stops decoding at the given byte offset, dropping anything buffered past
+    // it.
+    #[cfg(feature = "std")]
+    pub(crate) fn set_end(&mut self, end: u64) {
+        let buffered_end = self.offset + self.buffer.len() as u64;
+        if buffered_end > end {
+            let len = end.saturating_sub(self.offset) as usize;
+            self.buffer.truncate(len);
+        }
+        self.end = Some(end);
+    }
+
+    // Number of bytes, out of `len`, to read on the next refill, which stops
+    // at the end of the audio. The buffer only grows past its maximum for an
+    // ID3v2 tag, or if more input is needed anyway.
+    #[cfg(feature = "std")]
+    pub(crate) fn input_len(&self, len: usize) -> usize {
+        let room = self
+            .max_buffered
+            .max(self.wanted)
+            .saturating_sub(self.buffer.len());
+        let len = if room > 0 { len.min(room) } else { len };
+
+        match self.end {
+            Some(end) => {
+                let buffered_end = self.offset + self.buffer.len() as u64;
+                (end.saturating_sub(buffered_end)).min(len as u64) as usize
+            }
+            None => len,
+        }
+    }
+
+    // Forgets about everything buffered, to start decoding again from the
+    // given byte offset of the stream.
+    #[cfg(feature = "std")]
+    pub(crate) fn reset(&mut self, offset: u64) {
+        self.buffer.clear();
+        self.offset = offset;
+        self.finished = false;
+        self.skip = 0;
+        self.discard = 0;
+        self.run = None;
+        self.reset_gapless();
+        unsafe { ffi::mp3dec_init(&mut *self.decoder) }
+    }
+
+    // Locates the first frame of the stream, parsing the tags preceding it.
+    #[cfg(feature = "std")]
+    pub(crate) fn read_tags(&mut self) -> Result<(), Error> {
+        loop {
+            if self.tag_checked {
+                return Ok(());
+            }
+            if self.needs_input() {
+                return Err(Error::InsufficientData);
+            }
+
+            match self.check_tags() {
+                Err(Error::SkippedData) => {}
+                result => return self.at_end(result),
+            }
+        }
+    }
+
+    // Parses the header of the next frame without decoding it, returning the
+    // offset of the frame in the stream, its number of samples (per channel)
+    // and its sample rate.
+    #[cfg(feature = "std")]
+    pub(crate) fn scan_frame(&mut self) -> Result<(u64, u64, u32), Error> {
+        loop {
+            if self.needs_input() {
+                return Err(Error::InsufficientData);
+            }
+
+            match self.check_tags() {
+                Err(Error::SkippedData) => continue,
+                Err(e) => return self.at_end(Err(e)),
+                Ok(()) => {}
+            }
+
+            let (frame_info, samples) = self.peek_frame();
+            let samples = samples as u64;
+            let offset = self.offset + frame_info.frame_offset as u64;
+            self.consume(frame_info.frame_bytes as usize);
+
+            // minimp3 parses the headers of frames it was built not to
+            // decode, which are skipped when decoding.
+            let supported = !cfg!(feature = "only_mp3") || frame_info.layer == 3;
+            if samples > 0 && supported {
+                return Ok((offset, samples, frame_info.hz as u32));
+            }
+            if frame_info.frame_bytes == 0 {
+                return self.at_end(Err(Error::InsufficientData));
+            }
+        }
+    }
+
+    // Decodes and discards exactly one frame, regardless of whether it produced
+    // any audio.
+    #[cfg(feature = "std")]
+    pub(crate) fn skip_frame(&mut self) -> Result<(), Error> {
+        if self.needs_input() {
+            return Err(Error::InsufficientData);
+        }
+
+        // Skip the garbage before the frame, then decode the frame alone.
+        let (frame_info, samples) = self.peek_frame();
+        let garbage = if samples > 0 {
+            frame_info.frame_offset
+        } else {
+            frame_info.frame_bytes
+        } as usize;
+        let len = frame_info.frame_bytes as usize - garbage;
+        self.consume(garbage);
+
+        if len > 0 {
+            let mut pcm = [Sample::default(); MAX_SAMPLES_PER_FRAME];
+            let mut info: ffi::mp3dec_frame_info_t = unsafe { mem::zeroed() };
+            unsafe {
+                ffi::mp3dec_decode_frame(
+                    &mut *self.decoder,
+                    self.buffer.as_ptr(),
+                    len as _,
+                    pcm.as_mut_ptr(),
+                    &mut info,
+                );
+            }
+            self.consume(len);
+        }
+
+        if frame_info.frame_bytes > 0 {
+            Ok(())
+        } else {
+            self.at_end(Err(Error::InsufficientData))
+        }
+    }
+
+    // Turns the lack of data into the end of the stream once the input is
+    // finished.
+    fn at_end<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
+        match result {
+            Err(Error::InsufficientData) if self.finished => Err(Error::Eof),
+            result => result,
+        }
+    }
+
+    // Skips the ID3v2 tag at the start of the stream and locates the first
+    // frame, checking it for a VBR tag.
+    fn check_tags(&mut self) -> Result<(), Error> {
+        if !self.check_id3v2() {
+            return Err(Error::InsufficientData);
+        }
+        if !self.tag_checked {
+            self.check_tag()?;
+        }
+
+        Ok(())
+    }
+
+    // Skips the ID3v2 tag at the start of the stream in one step, parsing it
+    // on the way. Returns false if more data is needed to do so.
+    fn check_id3v2(&mut self) -> bool {
+        if self.id3v2_checked {
+            return true;
+        }
+        if self.buffer.len() < id3v2::HEADER_LEN {
+            return false;
+        }
+
+        if let Some(len) = id3v2::tag_len(&self.buffer) {
+            if !self.parse_id3v2 {
+                let buffered = len.min(self.buffer.len());
+                self.consume(buffered);
+                self.discard = (len - buffered) as u64;
+            } else if self.buffer.len() >= len {
+                self.id3v2 = id3v2::parse(&self.buffer[..len]);
+                self.wanted = 0;
+                self.consume(len);
+            } else if !self.finished {
+                self.wanted = len;
+                return false;
+            } else {
+                // The input ends before the tag does: its header is corrupt,
+                // or garbage which looks like one, so frames are looked for in
+                // what it would span, like in any garbage.
+                self.wanted = 0;
+            }
+        }
+
+        self.id3v2_checked = true;
+        true
+    }
+
+    // Locates the first frame of the stream and, if it holds a VBR tag, parses
+    // and consumes it so that it isn't decoded as a frame of silence.
+    fn check_tag(&mut self) -> Result<(), Error> {
+        let (frame_info, samples) = self.peek_frame();
+        self.skip_garbage(&frame_info, samples)?;
+
+        let frame = &self.buffer[..frame_info.frame_bytes as usize];
+        self.info = info::parse(frame);
+        self.tag_checked = true;
+        if self.info.is_some() {
+            self.consume(frame_info.frame_bytes as usize);
+        }
+
+        Ok(())
+    }
+
+    // Locates the next frame without decoding it, returning its number of
+    // samples (per channel), or 0 if no frame was found. The `frame_bytes` of
+    // the frame info is the end of the frame, the only part of the buffer to
+    // decode it from.
+    fn peek_frame(&mut self) -> (ffi::mp3dec_frame_info_t, usize) {
+        // minimp3 only takes a frame once the header of the next one confirms
+        // it, or if it ends the buffer exactly. Otherwise it resets itself,
+        // losing the bit reservoir, so the last frames have to be given to it
+        // alone once the input is finished.
+        if self.finished {
+            if let Some(len) = self.frame_len_at(0) {
+                return self.peek_frame_in(0, len);
+            }
+        }
+
+        let (frame_info, samples) = self.peek_frame_in(0, self.buffer.len());
+        if samples == 0 && frame_info.frame_bytes > 0 && self.finished {
+            // Nothing minimp3 can confirm is left: find any whole frame still
+            // buffered behind garbage, but not in the trailing tags.
+            let end = self.buffer.len() - trailers::tags_len(&self.buffer);
+            let frame = self
+                .last_header
+                .as_ref()
+                .and_then(|last| find_last_frame(&self.buffer[..end], last));
+            if let Some((offset, len)) = frame {
+                return self.peek_frame_in(offset, len);
+            }
+        }
+
+        (frame_info, samples)
+    }
+
+    // Locates the next frame in the `len` bytes at `offset` in the buffer.
+    // Passing a null PCM buffer makes minimp3 parse the frame header without
+    // decoding any audio, nor touching the bit reservoir.
+    fn peek_frame_in(&mut self, offset: usize, len: usize) -> (ffi::mp3dec_frame_info_t, usize) {
+        let mut frame_info: ffi::mp3dec_frame_info_t = unsafe { mem::zeroed() };
+        let samples = unsafe {
+            ffi::mp3dec_decode_frame(
+                &mut *self.decoder,
+                self.buffer[offset..].as_ptr(),
+                len as _,
+                ptr::null_mut(),
+                &mut frame_info,
+            ) as usize
+        };
+        frame_info.frame_bytes += offset as i32;
+
+        if samples > 0 {
+            frame_info.frame_offset += offset as i32;
+            let start = frame_info.frame_offset as usize;
+            self.last_header = self.buffer[start..start + header::HEADER_LEN]
+                .try_into()
+                .ok()
+                .and_then(FrameHeader::parse);
+        }
+
+        (frame_info, samples)
+    }
+
+    // Length of the frame starting at `offset` in the buffer, if a frame of the
+    // same stream as the last one found starts there and is wholly buffered.
+    fn frame_len_at(&self, offset: usize) -> Option<usize> {
+        self.last_header
+            .as_ref()?
+            .whole_frame_len(self.buffer.get(offset..)?)
+    }
+
+    // Skips the data preceding the frame located by `peek_frame`, recording
+    // it in the stats, so that the frame starts the buffer. Returns
+    // `SkippedData` if anything was skipped. In strict mode, a whole run of
+    // garbage is returned as `Corrupt` once it ends, before the frame
+    // following it is decoded.
+    fn skip_garbage(
+        &mut self,
+        frame_info: &ffi::mp3dec_frame_info_t,
+        samples: usize,
+    ) -> Result<(), Error> {
+        let skipped = if samples > 0 {
+            frame_info.frame_offset
+        } else {
+            frame_info.frame_bytes
+        } as usize;
+
+        if skipped > 0 {
+            // Without an end set by `read_trailing_tags`, the trailing tags of
+            // the stream are only found once the input is finished, as
+            // garbage.
+            let offset = self.offset;
+            let tags = if self.finished && skipped == self.buffer.len() {
+                trailers::tags_len(&self.buffer)
+            } else {
+                0
+            };
+            self.consume(skipped);
+
+            let len = skipped - tags;
+            if len > 0 {
+                self.stats.skip(offset, len);
+                let end = offset + len as u64;
+                match &mut self.run {
+                    Some(run) if run.end == offset => run.end = end,
+                    run => *run = Some(offset..end),
+                }
+            }
+        }
+
+        // The run ends at the next frame, or at the end of the input.
+        let ended = samples > 0 || (skipped == 0 && self.finished);
+        let run = if ended { self.run.take() } else { None };
+        if let Some(run) = run {
+            if samples > 0 {
+                self.stats.resync();
+            }
+            if self.strict {
+                return Err(Error::Corrupt {
+                    offset: run.start,
+                    len: (run.end - run.start) as usize,
+                });
+            }
+        }
+
+        match (skipped, samples) {
+            (0, 0) => Err(Error::InsufficientData),
+            (0, _) => Ok(()),
+            _ => Err(Error::SkippedData),
+        }
+    }
+
+    fn decode_frame_into(
+        &mut self,
+        pcm: &mut [Sample; MAX_SAMPLES_PER_FRAME],
+    ) -> Result<FrameInfo, Error> {
+        self.check_tags()?;
+
+        // Skip garbage on its own first, for it to be accounted for, then
+        // decode the frame which starts the buffer.
+        let (frame_info, samples) = self.peek_frame();
+        self.skip_garbage(&frame_info, samples)?;
+        let len = frame_info.frame_bytes;
+
+        let mut frame_info: ffi::mp3dec_frame_info_t = unsafe { mem::zeroed() };
+        let mut samples: usize = unsafe {
+            ffi::mp3dec_decode_frame(
+                &mut *self.decoder,
+                self.buffer.as_ptr(),
+                len,
+                pcm.as_mut_ptr(),
+                &mut frame_info,
+            ) as _
+        };
+        let channels = frame_info.channels as usize;
+
+        if samples > 0 {
+            self.stats.frames_decoded += 1;
+
+            // Discard leading samples left over from a seek or the encoder
+            // delay.
+            self.start_gapless();
+            if self.skip > 0 {
+                let skipped = self.skip.min(samples);
+                pcm.copy_within(skipped * channels..samples * channels, 0);
+                self.skip -= skipped;
+                samples -= skipped;
+            }
+
+            if samples > 0 {
+                samples = self.hold_padding(&mut pcm[..], samples, channels);
+            }
+        } else if frame_info.frame_bytes > 0 {
+            self.stats.frames_dropped += 1;
+        }
+
+        let info = if samples > 0 {
+            Some(FrameInfo::new(samples, &frame_info, &self.buffer))
+        } else {
+            None
+        };
+        self.consume(frame_info.frame_bytes as usize);
+
+        match info {
+            Some(info) => Ok(info),
+            None if frame_info.frame_bytes > 0 => Err(Error::SkippedData),
+            None => Err(Error::InsufficientData),
+        }
+    }
+
+    fn consume(&mut self, bytes: usize) {
+        self.buffer.consume(bytes);
+        self.offset += bytes as u64;
+    }
+}
+
+// Finds a whole frame of the same stream as one with the `last` header in
+// `data`, after its first byte, which either ends `data` or is followed by the
+// header of another frame, as minimp3 would want to confirm it. Returns its
+// offset and length.
+fn find_last_frame(data: &[u8], last: &FrameHeader) -> Option<(usize, usize)> {
+    (1..data.len()).find_map(|offset| {
+        let len = last.whole_frame_len(&data[offset..])?;
+        let next = offset + len;
+        let confirmed = next == data.len()
+            || data
+                .get(next..next + header::HEADER_LEN)
+                .and_then(|bytes| FrameHeader::parse(bytes.try_into().ok()?))
+                .is_some_and(|header| header.same_stream(last));
+
+        if confirmed {
+            Some((offset, len))
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    // The header of an ID3v2.3 tag of `len` bytes, header included.
+    fn id3v2_header(len: usize) -> Vec<u8> {
+        let size = len - id3v2::HEADER_LEN;
+        let mut header = b"ID3\x03\0\0".to_vec();
+        header.extend((0..4).rev().map(|i| (size >> (i * 7)) as u8 & 0x7f));
+        header
+    }
+
+    fn decode(core: &mut DecoderCore) -> Result<FrameInfo, Error> {
+        core.decode_into(&mut [Sample::default(); MAX_SAMPLES_PER_FRAME])
+    }
+
+    // MPEG 1 layer III, 128 kb/s, 44.1 kHz: 417 bytes long.
+    const HEADER: [u8; 4] = [0xff, 0xfb, 0x90, 0x00];
+
+    fn frame() -> Vec<u8> {
+        let mut frame = HEADER.to_vec();
+        frame.resize(417, 0);
+        frame
+    }
+
+    #[test]
+    fn last_frame_behind_garbage() {
+        let last = FrameHeader::parse(&HEADER).unwrap();
+        let mut data = vec![0x55; 10];
+        data.extend(frame());
+        assert_eq!(find_last_frame(&data, &last), Some((10, 417)));
+
+        // Followed by the start of another frame
+        data.extend_from_slice(&HEADER);
+        data.extend_from_slice(&[0; 10]);
+        assert_eq!(find_last_frame(&data, &last), Some((10, 417)));
+    }
+
+    #[test]
+    fn unconfirmed_last_frame() {
+        let last = FrameHeader::parse(&HEADER).unwrap();
+
+        // A header in binary data, such as the cover art of a trailing tag,
+        // isn't followed by another one.
+        let mut data = vec![0x55; 10];
+        data.extend(frame());
+        data.extend_from_slice(&[0x55; 50]);
+        assert_eq!(find_last_frame(&data, &last), None);
+
+        // Nor is a frame of another stream taken.
+        let mut data = vec![0x55; 10];
+        data.extend(frame());
+        data[12] = 0x94;
+        assert_eq!(find_last_frame(&data, &last), None);
+
+        // And the frame has to be whole.
+        let mut data = vec![0x55; 10];
+        data.extend(frame());
+        data.truncate(400);
+        assert_eq!(find_last_frame(&data, &last), None);
+    }
+
+    #[test]
+    fn id3v2_tag_is_buffered_whole() {
+        let mut core = DecoderCore::with_buffer_sizes(0, BUFFER_SIZE);
+        let mut tag = id3v2_header(100);
+        tag.resize(60, 0);
+        core.push(&tag);
+
+        assert!(matches!(decode(&mut core), Err(Error::InsufficientData)));
+        assert!(core.needs_input());
+        assert_eq!(core.buffered(), 60);
+
+        core.push(&[0; 40]);
+        assert!(!core.needs_input());
+        let _ = decode(&mut core);
+        assert!(core.id3v2().is_some());
+        assert_eq!(core.offset, 100);
+    }
+
+    #[test]
+    fn id3v2_tag_longer_than_input_is_garbage() {
+        let mut core = DecoderCore::with_buffer_sizes(0, BUFFER_SIZE);
+        let mut data = id3v2_header(1000);
+        data.resize(200, 0);
+        core.push(&data);
+        assert!(matches!(decode(&mut core), Err(Error::InsufficientData)));
+
+        // Once the input ends, what the tag would span is searched for frames.
+        core.finish();
+        let _ = decode(&mut core);
+        assert!(core.id3v2_checked);
+        assert!(core.id3v2().is_none());
+        assert_eq!(core.wanted, 0);
+    }
+
+    #[test]
+    fn id3v2_tag_is_dropped_unparsed() {
+        let mut core = DecoderCore::with_buffer_sizes(0, BUFFER_SIZE);
+        core.set_parse_id3v2(false);
+        let mut tag = id3v2_header(100);
+        tag.resize(30, 0);
+        core.push(&tag);
+
+        let _ = decode(&mut core);
+        assert_eq!(core.buffered(), 0);
+        assert_eq!(core.discard, 70);
+
+        // Only what follows the tag is buffered.
+        core.push(&[0; 50]);
+        core.push(&[0; 50]);
+        assert_eq!(core.discard, 0);
+        assert_eq!(core.buffered(), 30);
+        assert_eq!(core.offset, 100);
+        assert!(core.id3v2().is_none());
+    }
+}
@@ -1,20 +1,56 @@
-use thiserror::Error;
-
 /// Errors encountered by the MP3 decoder.
-#[derive(Debug, Error)]
+///
+/// It only implements `Display` and `std::error::Error` with the `std`
+/// feature.
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum Error {
+    #[cfg(feature = "std")]
     #[error("IO error: {0}")]
     /// An error caused by some IO operation required during decoding.
     Io(#[from] std::io::Error),
-    #[error("Insufficient data")]
+    #[cfg_attr(feature = "std", error("Insufficient data"))]
     /// The decoder tried to parse a frame from its internal buffer, but there
     /// was not enough.
     InsufficientData,
-    #[error("Skipped data")]
-    /// The decoder encountered data which was not a frame (ie, ID3 data), and
-    /// skipped it.
+    #[cfg_attr(feature = "std", error("Skipped data"))]
+    /// The decoder encountered data which was not a frame (ie, garbage between
+    /// frames), and skipped it.
     SkippedData,
-    #[error("End of reader")]
+    #[cfg_attr(feature = "std", error("Corrupt data: {len} bytes at offset {offset}"))]
+    /// The decoder skipped data which was neither a frame nor a tag. Only
+    /// returned in strict mode (see [`DecoderCore::set_strict`]), once for
+    /// each contiguous run of such data; decoding can go on after it.
+    ///
+    /// [`DecoderCore::set_strict`]: crate::DecoderCore::set_strict
+    Corrupt {
+        /// Byte offset of the skipped data in the stream.
+        offset: u64,
+        /// Number of bytes skipped.
+        len: usize,
+    },
+    #[cfg_attr(
+        feature = "std",
+        error("Stream format changed to {sample_rate} Hz, {channels} channels")
+    )]
+    /// The sample rate or the number of channels of the stream changed in the
+    /// middle of it, where it can't be handled (see `decode_all`).
+    FormatChanged {
+        /// The new sample rate.
+        sample_rate: i32,
+        /// The new number of channels.
+        channels: usize,
+    },
+    #[cfg_attr(feature = "std", error("minimp3_ex error {0}"))]
+    /// An error code (`MP3D_E_*`) returned by the `mp3dec_ex` API, see the
+    /// `ex` module.
+    Ex(i32),
+    #[cfg_attr(feature = "std", error("Reader would block"))]
+    /// The reader has no data available yet, having returned an
+    /// `io::ErrorKind::WouldBlock` error.
+    /// The decoder is left as it was, so reading can be tried again later.
+    WouldBlock,
+    #[cfg_attr(feature = "std", error("End of reader"))]
     /// The decoder has reached the end of the provided reader.
     Eof,
 }
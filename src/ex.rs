@@ -0,0 +1,419 @@
+//! Safe bindings to the `mp3dec_ex` API of minimp3 (`minimp3_ex.h`).
+//!
+//! Unlike [`Decoder`](crate::Decoder), the `mp3dec_ex` decoder reads whole
+//! streams by itself: it skips tags, trims the encoder delay and padding given
+//! by the LAME tag, and can seek to any sample, by first scanning the frames of
+//! the stream.
+
+use crate::{ffi, Error, Sample};
+use std::{
+    any::Any,
+    io::{self, Read, Seek, SeekFrom},
+    marker::PhantomData,
+    mem,
+    os::raw::{c_int, c_void},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+
+/// How [`Decoder::seek`] interprets positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekMode {
+    /// Positions are byte offsets in the stream.
+    Byte,
+    /// Positions are sample offsets (counting every channel) in the decoded
+    /// audio. Frames are indexed as needed, so seeks are sample accurate.
+    Sample,
+}
+
+impl SeekMode {
+    fn flags(self) -> c_int {
+        (match self {
+            SeekMode::Byte => ffi::MP3D_SEEK_TO_BYTE,
+            SeekMode::Sample => ffi::MP3D_SEEK_TO_SAMPLE,
+        }) as c_int
+    }
+}
+
+trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+// The callbacks given to minimp3, with the reader they read from.
+struct Io<'a> {
+    io: ffi::mp3dec_io_t,
+    reader: Box<dyn ReadSeek + 'a>,
+    // Position of the reader when it was given, which minimp3 sees as the
+    // start of the stream.
+    start: u64,
+    // The last error of the reader, reported by minimp3 as `MP3D_E_IOERROR`.
+    error: Option<io::Error>,
+    // A panic of the reader, which can't unwind through minimp3, to be
+    // resumed once it returns.
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<'a> Io<'a> {
+    // Boxes the callbacks, so that their address (given to minimp3) is stable.
+    fn new(mut reader: Box<dyn ReadSeek + 'a>) -> io::Result<*mut Io<'a>> {
+        let start = reader.stream_position()?;
+        let io = Box::into_raw(Box::new(Io {
+            io: unsafe { mem::zeroed() },
+            reader,
+            start,
+            error: None,
+            panic: None,
+        }));
+
+        unsafe {
+            (*io).io = ffi::mp3dec_io_t {
+                read: Some(read_callback),
+                read_data: io as *mut c_void,
+                seek: Some(seek_callback),
+                seek_data: io as *mut c_void,
+            };
+        }
+
+        Ok(io)
+    }
+
+    // Resumes the panic of the reader, if it panicked during the last call to
+    // minimp3.
+    fn resume_panic(&mut self) {
+        if let Some(payload) = self.panic.take() {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    fn take_error(&mut self, code: c_int) -> Error {
+        match self.error.take() {
+            Some(e) => Error::Io(e),
+            None => Error::Ex(code),
+        }
+    }
+}
+
+unsafe extern "C" fn read_callback(buf: *mut c_void, size: usize, user_data: *mut c_void) -> usize {
+    let io = &mut *(user_data as *mut Io);
+    let buf = slice::from_raw_parts_mut(buf as *mut u8, size);
+
+    // minimp3 takes short reads for the end of the stream.
+    let reader = &mut io.reader;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut read = 0;
+        while read < buf.len() {
+            match reader.read(&mut buf[read..]) {
+                Ok(0) => break,
+                Ok(len) => read += len,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(read)
+    }));
+
+    match result {
+        Ok(Ok(read)) => read,
+        // Reads longer than asked for are taken as errors.
+        Ok(Err(e)) => {
+            io.error = Some(e);
+            usize::MAX
+        }
+        Err(payload) => {
+            io.panic = Some(payload);
+            usize::MAX
+        }
+    }
+}
+
+unsafe extern "C" fn seek_callback(position: u64, user_data: *mut c_void) -> c_int {
+    let io = &mut *(user_data as *mut Io);
+    let reader = &mut io.reader;
+    let position = io.start.saturating_add(position);
+    match panic::catch_unwind(AssertUnwindSafe(|| reader.seek(SeekFrom::Start(position)))) {
+        Ok(Ok(_)) => 0,
+        Ok(Err(e)) => {
+            io.error = Some(e);
+            -1
+        }
+        Err(payload) => {
+            io.panic = Some(payload);
+            -1
+        }
+    }
+}
+
+/// A decoder using the `mp3dec_ex` API, reading from a slice or from a
+/// seekable reader.
+pub struct Decoder<'a> {
+    decoder: Box<ffi::mp3dec_ex_t>,
+    // Only set when decoding from a reader.
+    io: *mut Io<'a>,
+    _data: PhantomData<&'a [u8]>,
+}
+
+impl<'a> Decoder<'a> {
+    /// Opens the stream held in `data`.
+    pub fn from_slice(data: &'a [u8], mode: SeekMode) -> Result<Self, Error> {
+        let mut decoder = Self {
+            decoder: unsafe { Box::new(mem::zeroed()) },
+            io: ptr::null_mut(),
+            _data: PhantomData,
+        };
+
+        let code = unsafe {
+            ffi::mp3dec_ex_open_buf(
+                &mut *decoder.decoder,
+                data.as_ptr(),
+                data.len(),
+                mode.flags(),
+            )
+        };
+        if code != 0 {
+            return Err(Error::Ex(code));
+        }
+
+        Ok(decoder)
+    }
+
+    /// Opens the stream read from `reader`, starting at its current position.
+    /// Byte offsets, and the seeks minimp3 makes, are relative to that
+    /// position.
+    pub fn from_reader<R: Read + Seek + 'a>(reader: R, mode: SeekMode) -> Result<Self, Error> {
+        let mut decoder = Self {
+            decoder: unsafe { Box::new(mem::zeroed()) },
+            io: Io::new(Box::new(reader))?,
+            _data: PhantomData,
+        };
+
+        let code = unsafe {
+            ffi::mp3dec_ex_open_cb(&mut *decoder.decoder, &mut (*decoder.io).io, mode.flags())
+        };
+        decoder.resume_panic();
+        if code != 0 {
+            return Err(decoder.error(code));
+        }
+
+        Ok(decoder)
+    }
+
+    /// The total number of samples of the stream (counting every channel),
+    /// after trimming the encoder delay and padding.
+    pub fn samples(&self) -> u64 {
+        self.decoder.samples
+    }
+
+    /// Sample rate of the stream.
+    pub fn sample_rate(&self) -> i32 {
+        self.decoder.info.hz
+    }
+
+    /// Number of channels of the stream.
+    pub fn channels(&self) -> usize {
+        self.decoder.info.channels as usize
+    }
+
+    /// MPEG layer of the stream.
+    pub fn layer(&self) -> usize {
+        self.decoder.info.layer as usize
+    }
+
+    /// Bitrate of the last frame read, in kb/s.
+    pub fn bitrate(&self) -> i32 {
+        self.decoder.info.bitrate_kbps
+    }
+
+    /// Seeks to `position`, which is a byte or sample offset depending on the
+    /// [`SeekMode`] the decoder was opened with.
+    pub fn seek(&mut self, position: u64) -> Result<(), Error> {
+        let code = unsafe { ffi::mp3dec_ex_seek(&mut *self.decoder, position) };
+        self.resume_panic();
+        if code != 0 {
+            return Err(self.error(code));
+        }
+
+        Ok(())
+    }
+
+    /// Decodes interleaved samples into `buf`, returning how many were
+    /// decoded. Fewer samples than `buf` holds are only returned at the end of
+    /// the stream.
+    pub fn read(&mut self, buf: &mut [Sample]) -> Result<usize, Error> {
+        let samples =
+            unsafe { ffi::mp3dec_ex_read(&mut *self.decoder, buf.as_mut_ptr(), buf.len()) };
+        self.resume_panic();
+
+        if samples < buf.len() && self.decoder.last_error != 0 {
+            let code = mem::replace(&mut self.decoder.last_error, 0);
+            return Err(self.error(code));
+        }
+
+        Ok(samples)
+    }
+
+    fn resume_panic(&mut self) {
+        if !self.io.is_null() {
+            unsafe { (*self.io).resume_panic() }
+        }
+    }
+
+    fn error(&mut self, code: c_int) -> Error {
+        if self.io.is_null() {
+            Error::Ex(code)
+        } else {
+            unsafe { (*self.io).take_error(code) }
+        }
+    }
+}
+
+impl Drop for Decoder<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::mp3dec_ex_close(&mut *self.decoder);
+            if !self.io.is_null() {
+                drop(Box::from_raw(self.io));
+            }
+        }
+    }
+}
+
+/// Calls `callback` with the offset (relative to the current position of
+/// `reader`) and the bytes of every frame read from `reader`, without
+/// decoding them, using `mp3dec_iterate_cb`. Iteration
+/// stops early if `callback` returns `false`. If `callback` or `reader`
+/// panics, iteration stops too and the panic is resumed once minimp3 returns.
+pub fn iterate<R, F>(reader: R, callback: F) -> Result<(), Error>
+where
+    R: Read + Seek,
+    F: FnMut(u64, &[u8]) -> bool,
+{
+    struct State<'a, F> {
+        io: *mut Io<'a>,
+        callback: F,
+        // A panic of `callback`, resumed once minimp3 returns.
+        panic: Option<Box<dyn Any + Send>>,
+    }
+
+    unsafe extern "C" fn iterate_callback<F: FnMut(u64, &[u8]) -> bool>(
+        user_data: *mut c_void,
+        frame: *const u8,
+        frame_size: c_int,
+        _free_format_bytes: c_int,
+        _buf_size: usize,
+        offset: u64,
+        _info: *mut ffi::mp3dec_frame_info_t,
+    ) -> c_int {
+        let state = &mut *(user_data as *mut State<F>);
+        let frame = slice::from_raw_parts(frame, frame_size as usize);
+        let callback = &mut state.callback;
+        match panic::catch_unwind(AssertUnwindSafe(|| callback(offset, frame))) {
+            Ok(true) => 0,
+            Ok(false) => 1,
+            Err(payload) => {
+                state.panic = Some(payload);
+                1
+            }
+        }
+    }
+
+    let mut state = State {
+        io: Io::new(Box::new(reader))?,
+        callback,
+        panic: None,
+    };
+    let mut buf = vec![0; ffi::MINIMP3_BUF_SIZE as usize];
+
+    let code = unsafe {
+        ffi::mp3dec_iterate_cb(
+            &mut (*state.io).io,
+            buf.as_mut_ptr(),
+            buf.len(),
+            Some(iterate_callback::<F>),
+            &mut state as *mut State<F> as *mut c_void,
+        )
+    };
+
+    let mut io = unsafe { Box::from_raw(state.io) };
+    if let Some(payload) = state.panic {
+        panic::resume_unwind(payload);
+    }
+    io.resume_panic();
+
+    match io.error {
+        Some(e) => Err(Error::Io(e)),
+        None if code < 0 => Err(Error::Ex(code)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    struct Panicking;
+
+    impl Read for Panicking {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            panic!("read");
+        }
+    }
+
+    impl Seek for Panicking {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            match pos {
+                SeekFrom::Current(0) => Ok(0),
+                _ => Err(io::Error::other("seek")),
+            }
+        }
+    }
+
+    // Calls the callbacks as minimp3 would, then frees the `Io`.
+    fn with_io<'a>(reader: impl Read + Seek + 'a, f: impl FnOnce(&mut Io<'a>, *mut c_void)) {
+        let io = Io::new(Box::new(reader)).unwrap();
+        f(unsafe { &mut *io }, io as *mut c_void);
+        drop(unsafe { Box::from_raw(io) });
+    }
+
+    fn read(user_data: *mut c_void, buf: &mut [u8]) -> usize {
+        unsafe { read_callback(buf.as_mut_ptr() as *mut c_void, buf.len(), user_data) }
+    }
+
+    #[test]
+    fn seeks_from_the_starting_position() {
+        let mut cursor = Cursor::new(vec![0, 1, 2, 3, 4, 5]);
+        cursor.set_position(2);
+        with_io(cursor, |_, user_data| {
+            let mut buf = [0; 2];
+            assert_eq!(read(user_data, &mut buf), 2);
+            assert_eq!(buf, [2, 3]);
+
+            assert_eq!(unsafe { seek_callback(0, user_data) }, 0);
+            assert_eq!(read(user_data, &mut buf), 2);
+            assert_eq!(buf, [2, 3]);
+
+            assert_eq!(unsafe { seek_callback(3, user_data) }, 0);
+            assert_eq!(read(user_data, &mut buf), 1);
+            assert_eq!(buf[0], 5);
+        });
+    }
+
+    #[test]
+    fn errors_are_kept() {
+        with_io(Panicking, |io, user_data| {
+            assert_eq!(unsafe { seek_callback(0, user_data) }, -1);
+            assert!(matches!(io.take_error(-1), Error::Io(_)));
+            assert!(matches!(io.take_error(-1), Error::Ex(-1)));
+        });
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        with_io(Panicking, |io, user_data| {
+            assert_eq!(read(user_data, &mut [0; 4]), usize::MAX);
+            assert!(io.panic.is_some());
+            assert!(panic::catch_unwind(AssertUnwindSafe(|| io.resume_panic())).is_err());
+            assert!(io.panic.is_none());
+        });
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{Decoder, Error, Frame};
+use std::io;
+#[cfg(feature = "async_tokio")]
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// An iterator over the frames of a [`Decoder`], created by
+/// [`Decoder::frames`].
+///
+/// It ends once the end of the reader is reached, or right after yielding any
+/// other error than [`Error::Corrupt`] or [`Error::WouldBlock`]. With the
+/// `async_tokio` feature, it is also a `futures_core::Stream` of
+/// frames when the reader is a `tokio::io::AsyncRead`.
+///
+/// With a non-blocking reader, it keeps yielding `Err(Error::WouldBlock)` for
+/// as long as the reader has no data, so a plain `for` loop over it spins
+/// until data arrives. Wait for the reader to be ready (with `poll` or an
+/// event loop) when `WouldBlock` is yielded.
+pub struct Frames<'a, R> {
+    decoder: &'a mut Decoder<R>,
+    done: bool,
+}
+
+impl<R> Decoder<R> {
+    /// Return an iterator (or stream) over the remaining frames of the
+    /// reader.
+    pub fn frames(&mut self) -> Frames<'_, R> {
+        Frames {
+            decoder: self,
+            done: false,
+        }
+    }
+}
+
+impl<R> Frames<'_, R> {
+    fn next_item(&mut self, result: Result<Frame, Error>) -> Option<Result<Frame, Error>> {
+        match result {
+            Ok(frame) => Some(Ok(frame)),
+            // Decoding goes on after corrupt data in strict mode, and once a
+            // non-blocking reader has data again.
+            Err(e @ Error::Corrupt { .. }) | Err(e @ Error::WouldBlock) => Some(Err(e)),
+            Err(Error::Eof) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<R: io::Read> Iterator for Frames<'_, R> {
+    type Item = Result<Frame, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.decoder.next_frame();
+        self.next_item(result)
+    }
+}
+
+#[cfg(feature = "async_tokio")]
+impl<R: tokio::io::AsyncRead + Unpin> futures_core::Stream for Frames<'_, R> {
+    type Item = Result<Frame, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        this.decoder
+            .poll_next_frame::<crate::TokioIo>(cx)
+            .map(|result| this.next_item(result))
+    }
+}
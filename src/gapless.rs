@@ -0,0 +1,195 @@
+#[cfg(feature = "std")]
+use crate::Decoder;
+use crate::{DecoderCore, Sample};
+use alloc::vec::Vec;
+
+// Delay introduced by the decoder itself (the MDCT and the synthesis
+// filterbank), on top of the encoder delay stored in the LAME tag.
+const DECODER_DELAY: usize = 528 + 1;
+
+pub(crate) struct Gapless {
+    // Encoder delay and padding given by the user, overriding the LAME tag.
+    overridden: Option<(usize, usize)>,
+    // Whether `delay` and `padding` have been resolved and the leading delay
+    // scheduled for trimming.
+    started: bool,
+    // Samples (per channel) to trim at the start of the stream.
+    pub(crate) delay: usize,
+    // Samples (per channel) to trim at the end of the stream.
+    padding: usize,
+    // The last `padding` samples decoded so far, which are only emitted once
+    // more audio follows them.
+    held: Vec<Sample>,
+}
+
+#[cfg(feature = "std")]
+impl<R> Decoder<R> {
+    /// Enables or disables gapless playback.
+    ///
+    /// When enabled, the encoder delay and padding recorded in the LAME tag of
+    /// the stream (see [`StreamInfo`](crate::StreamInfo)) are trimmed from the
+    /// start and the end of the decoded audio, across frame boundaries, so
+    /// that consecutive tracks can be played back to back without gaps.
+    /// Streams without a LAME tag are decoded untouched, unless an override
+    /// is given with [`set_gapless_override`](Decoder::set_gapless_override).
+    ///
+    /// Sample positions given to the seeking methods are relative to the
+    /// trimmed audio. This must be set before decoding the first frame or
+    /// seeking: it is ignored afterwards, as samples were already trimmed, or
+    /// not, by then.
+    pub fn set_gapless(&mut self, enabled: bool) {
+        self.core.set_gapless(enabled);
+    }
+
+    /// Enables gapless playback using the given encoder delay and padding (in
+    /// samples per channel, as they would be stored in a LAME tag) instead of
+    /// the ones from the LAME tag of the stream.
+    ///
+    /// See [`set_gapless`](Decoder::set_gapless).
+    pub fn set_gapless_override(&mut self, encoder_delay: u16, encoder_padding: u16) {
+        self.core
+            .set_gapless_override(encoder_delay, encoder_padding);
+    }
+}
+
+impl DecoderCore {
+    /// Enables or disables gapless playback.
+    ///
+    /// When enabled, the encoder delay and padding recorded in the LAME tag of
+    /// the stream (see [`StreamInfo`](crate::StreamInfo)) are trimmed from the
+    /// start and the end of the decoded audio, across frame boundaries.
+    /// Streams without a LAME tag are decoded untouched, unless an override
+    /// is given with
+    /// [`set_gapless_override`](DecoderCore::set_gapless_override).
+    ///
+    /// This must be set before decoding the first frame: it is ignored
+    /// afterwards.
+    pub fn set_gapless(&mut self, enabled: bool) {
+        if self.gapless_started() {
+            return;
+        }
+
+        self.gapless = if enabled {
+            Some(Gapless {
+                overridden: None,
+                started: false,
+                delay: 0,
+                padding: 0,
+                held: Vec::new(),
+            })
+        } else {
+            None
+        };
+    }
+
+    /// Enables gapless playback using the given encoder delay and padding (in
+    /// samples per channel, as they would be stored in a LAME tag) instead of
+    /// the ones from the LAME tag of the stream.
+    ///
+    /// See [`set_gapless`](DecoderCore::set_gapless).
+    pub fn set_gapless_override(&mut self, encoder_delay: u16, encoder_padding: u16) {
+        if self.gapless_started() {
+            return;
+        }
+
+        self.set_gapless(true);
+        if let Some(gapless) = &mut self.gapless {
+            gapless.overridden = Some((encoder_delay as usize, encoder_padding as usize));
+        }
+    }
+
+    // Whether audio was already decoded, or a seek made, with the current
+    // gapless setting.
+    fn gapless_started(&self) -> bool {
+        let stats = &self.stats;
+        let started = self.gapless.as_ref().is_some_and(|gapless| gapless.started);
+        started || stats.frames_decoded + stats.frames_dropped > 0
+    }
+
+    // Resolves the delay and padding to trim, once the VBR tag of the stream
+    // has been checked, and schedules the leading delay for trimming.
+    pub(crate) fn start_gapless(&mut self) {
+        let lame = self.info.as_ref().and_then(|info| info.lame.as_ref());
+        let gapless = match &mut self.gapless {
+            Some(gapless) if !gapless.started => gapless,
+            _ => return,
+        };
+
+        let trim = gapless.overridden.or_else(|| {
+            lame.map(|lame| (lame.encoder_delay as usize, lame.encoder_padding as usize))
+        });
+        if let Some((delay, padding)) = trim {
+            gapless.delay = delay + DECODER_DELAY;
+            gapless.padding = padding.saturating_sub(DECODER_DELAY);
+        }
+
+        gapless.started = true;
+        self.skip += gapless.delay;
+    }
+
+    // Holds back the trailing padding of the `samples` decoded in `pcm`,
+    // emitting whatever was held back before it instead. Returns the number of
+    // samples left in `pcm`.
+    pub(crate) fn hold_padding(
+        &mut self,
+        pcm: &mut [Sample],
+        samples: usize,
+        channels: usize,
+    ) -> usize {
+        let gapless = match &mut self.gapless {
+            Some(gapless) if gapless.padding > 0 => gapless,
+            _ => return samples,
+        };
+
+        gapless.held.extend_from_slice(&pcm[..samples * channels]);
+        let emit = gapless
+            .held
+            .len()
+            .saturating_sub(gapless.padding * channels)
+            .min(pcm.len());
+        pcm[..emit].copy_from_slice(&gapless.held[..emit]);
+        gapless.held.drain(..emit);
+
+        emit / channels
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn reset_gapless(&mut self) {
+        if let Some(gapless) = &mut self.gapless {
+            gapless.held.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_before_decoding() {
+        let mut core = DecoderCore::new();
+        core.set_gapless(true);
+        core.set_gapless_override(576, 1152);
+        assert_eq!(core.gapless.as_ref().unwrap().overridden, Some((576, 1152)));
+        core.set_gapless(false);
+        assert!(core.gapless.is_none());
+    }
+
+    #[test]
+    fn ignored_once_started() {
+        let mut core = DecoderCore::new();
+        core.set_gapless(true);
+        core.start_gapless();
+        core.set_gapless(true);
+        assert!(core.gapless.as_ref().unwrap().started);
+        core.set_gapless(false);
+        core.set_gapless_override(576, 1152);
+        assert!(core.gapless.as_ref().unwrap().overridden.is_none());
+
+        let mut core = DecoderCore::new();
+        core.stats.frames_decoded = 1;
+        core.set_gapless(true);
+        core.set_gapless_override(576, 1152);
+        assert!(core.gapless.is_none());
+    }
+}
@@ -0,0 +1,433 @@
+use core::convert::TryInto;
+
+// Size of a MPEG audio frame header in bytes.
+pub(crate) const HEADER_LEN: usize = 4;
+
+/// Bitrates in kb/s, by version and layer, indexed by the bitrate index of the
+/// header. Index 0 is the free format.
+const BITRATES: [[[u16; 15]; 3]; 2] = [
+    // MPEG 1
+    [
+        [
+            0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+        ],
+        [
+            0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+        ],
+        [
+            0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+        ],
+    ],
+    // MPEG 2 and 2.5
+    [
+        [
+            0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+        ],
+        [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+        [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+    ],
+];
+
+/// Sample rates in hertz of MPEG 1, indexed by the sample rate index of the
+/// header. MPEG 2 halves them and MPEG 2.5 quarters them.
+const SAMPLE_RATES: [u32; 3] = [44100, 48000, 32000];
+
+/// MPEG audio version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    /// MPEG 1 (ISO/IEC 11172-3).
+    Mpeg1,
+    /// MPEG 2 (ISO/IEC 13818-3), low sample rates.
+    Mpeg2,
+    /// MPEG 2.5, an unofficial extension for very low sample rates.
+    Mpeg25,
+}
+
+/// MPEG audio layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// Layer I.
+    Layer1,
+    /// Layer II.
+    Layer2,
+    /// Layer III, better known as MP3.
+    Layer3,
+}
+
+impl Layer {
+    /// Whether frames of this layer are decoded. Only layer III is with the
+    /// `only_mp3` feature.
+    pub fn is_supported(self) -> bool {
+        !cfg!(feature = "only_mp3") || self == Layer::Layer3
+    }
+}
+
+/// Channel mode of a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelMode {
+    /// Two independently coded channels.
+    Stereo,
+    /// Two channels coded together, see [`FrameHeader::mode_extension`].
+    JointStereo,
+    /// Two independent mono channels.
+    DualChannel,
+    /// A single channel.
+    Mono,
+}
+
+/// De-emphasis to apply to the decoded audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emphasis {
+    /// No emphasis.
+    None,
+    /// 50/15 microseconds emphasis.
+    FiftyFifteen,
+    /// Reserved value.
+    Reserved,
+    /// CCITT J.17 emphasis.
+    CcittJ17,
+}
+
+/// The header of a MPEG audio frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    /// MPEG version.
+    pub version: Version,
+    /// MPEG layer.
+    pub layer: Layer,
+    /// Whether the header is followed by a 16 bit CRC.
+    pub crc: bool,
+    /// Bitrate in kb/s, or `None` for free format frames.
+    pub bitrate: Option<u32>,
+    /// Sample rate in hertz.
+    pub sample_rate: u32,
+    /// Whether the frame is padded with an extra slot.
+    pub padding: bool,
+    /// Private bit, free for application use.
+    pub private: bool,
+    /// Channel mode.
+    pub channel_mode: ChannelMode,
+    /// Mode extension, describing how joint stereo is coded.
+    pub mode_extension: u8,
+    /// Whether the audio is copyrighted.
+    pub copyright: bool,
+    /// Whether this is the original media, as opposed to a copy.
+    pub original: bool,
+    /// Emphasis.
+    pub emphasis: Emphasis,
+}
+
+impl FrameHeader {
+    /// Parses a frame header. Returns `None` if `bytes` don't hold a valid
+    /// header.
+    pub fn parse(bytes: &[u8; 4]) -> Option<Self> {
+        if bytes[0] != 0xff || bytes[1] & 0xe0 != 0xe0 {
+            return None;
+        }
+
+        let version = match (bytes[1] >> 3) & 0x3 {
+            0 => Version::Mpeg25,
+            2 => Version::Mpeg2,
+            3 => Version::Mpeg1,
+            _ => return None,
+        };
+        let layer = match (bytes[1] >> 1) & 0x3 {
+            1 => Layer::Layer3,
+            2 => Layer::Layer2,
+            3 => Layer::Layer1,
+            _ => return None,
+        };
+
+        let bitrate_index = (bytes[2] >> 4) as usize;
+        let sample_rate_index = ((bytes[2] >> 2) & 0x3) as usize;
+        if bitrate_index == 15 || sample_rate_index == 3 {
+            return None;
+        }
+
+        let table = if version == Version::Mpeg1 { 0 } else { 1 };
+        let bitrate = BITRATES[table][layer as usize][bitrate_index];
+        let sample_rate = SAMPLE_RATES[sample_rate_index]
+            >> match version {
+                Version::Mpeg1 => 0,
+                Version::Mpeg2 => 1,
+                Version::Mpeg25 => 2,
+            };
+
+        Some(Self {
+            version,
+            layer,
+            crc: bytes[1] & 0x1 == 0,
+            bitrate: if bitrate == 0 {
+                None
+            } else {
+                Some(u32::from(bitrate))
+            },
+            sample_rate,
+            padding: bytes[2] & 0x2 != 0,
+            private: bytes[2] & 0x1 != 0,
+            channel_mode: match bytes[3] >> 6 {
+                0 => ChannelMode::Stereo,
+                1 => ChannelMode::JointStereo,
+                2 => ChannelMode::DualChannel,
+                _ => ChannelMode::Mono,
+            },
+            mode_extension: (bytes[3] >> 4) & 0x3,
+            copyright: bytes[3] & 0x8 != 0,
+            original: bytes[3] & 0x4 != 0,
+            emphasis: match bytes[3] & 0x3 {
+                0 => Emphasis::None,
+                1 => Emphasis::FiftyFifteen,
+                2 => Emphasis::Reserved,
+                _ => Emphasis::CcittJ17,
+            },
+        })
+    }
+
+    /// The number of channels.
+    pub fn channels(&self) -> usize {
+        match self.channel_mode {
+            ChannelMode::Mono => 1,
+            _ => 2,
+        }
+    }
+
+    /// The number of samples (per channel) in the frame.
+    pub fn samples_per_frame(&self) -> usize {
+        match (self.layer, self.version) {
+            (Layer::Layer1, _) => 384,
+            (Layer::Layer2, _) | (Layer::Layer3, Version::Mpeg1) => 1152,
+            (Layer::Layer3, _) => 576,
+        }
+    }
+
+    /// The size of the whole frame in bytes, including this header, or `None`
+    /// for free format frames, whose size can only be found by looking for the
+    /// next header.
+    pub fn frame_len(&self) -> Option<usize> {
+        let bitrate = self.bitrate? as usize * 1000;
+        let sample_rate = self.sample_rate as usize;
+        let padding = self.padding as usize;
+
+        Some(match self.layer {
+            Layer::Layer1 => (12 * bitrate / sample_rate + padding) * 4,
+            _ => self.samples_per_frame() / 8 * bitrate / sample_rate + padding,
+        })
+    }
+
+    /// The size in bytes of the side information following the header (and
+    /// CRC) of a layer III frame.
+    pub fn side_info_len(&self) -> usize {
+        match (self.version, self.channel_mode) {
+            (Version::Mpeg1, ChannelMode::Mono) => 17,
+            (Version::Mpeg1, _) => 32,
+            (_, ChannelMode::Mono) => 9,
+            _ => 17,
+        }
+    }
+
+    // Length of the frame of the same stream as one with this header which
+    // starts `data`, if it is wholly held in `data`.
+    pub(crate) fn whole_frame_len(&self, data: &[u8]) -> Option<usize> {
+        let header = FrameHeader::parse(data.get(..HEADER_LEN)?.try_into().ok()?)?;
+        let len = header.frame_len()?;
+
+        if header.same_stream(self) && len <= data.len() {
+            Some(len)
+        } else {
+            None
+        }
+    }
+
+    // Whether a frame with this header can belong to the same stream as one
+    // with the `other` header, comparing the same fields as minimp3 does.
+    pub(crate) fn same_stream(&self, other: &FrameHeader) -> bool {
+        self.version == other.version
+            && self.layer == other.layer
+            && self.sample_rate == other.sample_rate
+            && self.bitrate.is_some() == other.bitrate.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(bytes: [u8; 4]) -> Option<FrameHeader> {
+        FrameHeader::parse(&bytes)
+    }
+
+    // `hdr_frame_bytes` and `hdr_padding` of minimp3, with its own tables.
+    fn minimp3_frame_bytes(h: [u8; 4]) -> usize {
+        const HALFRATE: [[[usize; 15]; 3]; 2] = [
+            [
+                [0, 4, 8, 12, 16, 20, 24, 28, 32, 40, 48, 56, 64, 72, 80],
+                [0, 4, 8, 12, 16, 20, 24, 28, 32, 40, 48, 56, 64, 72, 80],
+                [0, 16, 24, 28, 32, 40, 48, 56, 64, 72, 80, 88, 96, 112, 128],
+            ],
+            [
+                [0, 16, 20, 24, 28, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160],
+                [
+                    0, 16, 24, 28, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192,
+                ],
+                [
+                    0, 16, 32, 48, 64, 80, 96, 112, 128, 144, 160, 176, 192, 208, 224,
+                ],
+            ],
+        ];
+        let mpeg1 = h[1] & 0x8 != 0;
+        let not_mpeg25 = h[1] & 0x10 != 0;
+        let layer = ((h[1] >> 1) & 3) as usize;
+        let layer1 = layer == 3;
+
+        let kbps = 2 * HALFRATE[mpeg1 as usize][layer - 1][(h[2] >> 4) as usize];
+        let hz = [44100, 48000, 32000][((h[2] >> 2) & 3) as usize]
+            >> !mpeg1 as usize
+            >> !not_mpeg25 as usize;
+        let samples = if layer1 {
+            384
+        } else if h[1] & 14 == 2 {
+            576
+        } else {
+            1152
+        };
+
+        let mut bytes = samples * kbps * 125 / hz;
+        if layer1 {
+            bytes &= !3;
+        }
+        let padding = if h[2] & 0x2 != 0 {
+            if layer1 {
+                4
+            } else {
+                1
+            }
+        } else {
+            0
+        };
+        bytes + padding
+    }
+
+    #[test]
+    fn mpeg1_layer3() {
+        let header = parse([0xff, 0xfb, 0x90, 0x64]).unwrap();
+        assert_eq!(header.version, Version::Mpeg1);
+        assert_eq!(header.layer, Layer::Layer3);
+        assert!(!header.crc);
+        assert_eq!(header.bitrate, Some(128));
+        assert_eq!(header.sample_rate, 44100);
+        assert!(!header.padding);
+        assert_eq!(header.channel_mode, ChannelMode::JointStereo);
+        assert_eq!(header.mode_extension, 2);
+        assert!(header.original);
+        assert_eq!(header.samples_per_frame(), 1152);
+        assert_eq!(header.frame_len(), Some(417));
+        assert_eq!(header.side_info_len(), 32);
+
+        let padded = parse([0xff, 0xfb, 0x92, 0x64]).unwrap();
+        assert!(padded.padding);
+        assert_eq!(padded.frame_len(), Some(418));
+    }
+
+    #[test]
+    fn layer1_padding_is_a_slot_of_four_bytes() {
+        // 384 kb/s, 48 kHz
+        let header = parse([0xff, 0xff, 0xc4, 0x00]).unwrap();
+        assert_eq!(header.layer, Layer::Layer1);
+        assert_eq!(header.samples_per_frame(), 384);
+        assert_eq!(header.frame_len(), Some(384));
+
+        let padded = parse([0xff, 0xff, 0xc6, 0x00]).unwrap();
+        assert_eq!(padded.frame_len(), Some(388));
+    }
+
+    #[test]
+    fn mpeg25() {
+        // 64 kb/s, 11025 Hz, mono, with a CRC
+        let header = parse([0xff, 0xe2, 0x80, 0xc0]).unwrap();
+        assert_eq!(header.version, Version::Mpeg25);
+        assert!(header.crc);
+        assert_eq!(header.bitrate, Some(64));
+        assert_eq!(header.sample_rate, 11025);
+        assert_eq!(header.channels(), 1);
+        assert_eq!(header.samples_per_frame(), 576);
+        assert_eq!(header.frame_len(), Some(417));
+        assert_eq!(header.side_info_len(), 9);
+    }
+
+    #[test]
+    fn free_format() {
+        let header = parse([0xff, 0xfb, 0x00, 0x00]).unwrap();
+        assert_eq!(header.bitrate, None);
+        assert_eq!(header.frame_len(), None);
+    }
+
+    #[test]
+    fn reserved_values() {
+        // Version
+        assert_eq!(parse([0xff, 0xeb, 0x90, 0x00]), None);
+        // Layer
+        assert_eq!(parse([0xff, 0xf9, 0x90, 0x00]), None);
+        // Bitrate
+        assert_eq!(parse([0xff, 0xfb, 0xf0, 0x00]), None);
+        // Sample rate
+        assert_eq!(parse([0xff, 0xfb, 0x9c, 0x00]), None);
+        // Sync
+        assert_eq!(parse([0xff, 0xdb, 0x90, 0x00]), None);
+        assert_eq!(parse([0xfe, 0xfb, 0x90, 0x00]), None);
+    }
+
+    #[test]
+    fn frame_len_matches_minimp3() {
+        for b1 in 0xe0..=0xff {
+            for b2 in 0..=0xff {
+                let bytes = [0xff, b1, b2, 0];
+                if let Some(len) = parse(bytes).and_then(|header| header.frame_len()) {
+                    assert_eq!(len, minimp3_frame_bytes(bytes), "{:02x?}", bytes);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn side_info_len() {
+        let len = |b1, b3| parse([0xff, b1, 0x90, b3]).unwrap().side_info_len();
+        assert_eq!(len(0xfb, 0x00), 32);
+        assert_eq!(len(0xfb, 0xc0), 17);
+        assert_eq!(len(0xf3, 0x00), 17);
+        assert_eq!(len(0xf3, 0xc0), 9);
+    }
+
+    #[test]
+    fn same_stream() {
+        let header = parse([0xff, 0xfb, 0x90, 0x00]).unwrap();
+        // Other bitrate, padding and channel mode
+        let other = parse([0xff, 0xfb, 0xb2, 0xc0]).unwrap();
+        assert!(header.same_stream(&other));
+
+        // Other sample rate, version, layer and free format
+        for bytes in [
+            [0xff, 0xfb, 0x94, 0x00],
+            [0xff, 0xf3, 0x90, 0x00],
+            [0xff, 0xfd, 0x90, 0x00],
+            [0xff, 0xfb, 0x00, 0x00],
+        ] {
+            assert!(
+                !header.same_stream(&parse(bytes).unwrap()),
+                "{:02x?}",
+                bytes
+            );
+        }
+    }
+
+    #[test]
+    fn whole_frame_len() {
+        let header = parse([0xff, 0xfb, 0x90, 0x00]).unwrap();
+        let mut data = [0; 418];
+        data[..4].copy_from_slice(&[0xff, 0xfb, 0x90, 0x00]);
+
+        assert_eq!(header.whole_frame_len(&data), Some(417));
+        assert_eq!(header.whole_frame_len(&data[..417]), Some(417));
+        assert_eq!(header.whole_frame_len(&data[..416]), None);
+        assert_eq!(header.whole_frame_len(&data[1..]), None);
+    }
+}
@@ -0,0 +1,58 @@
+use alloc::{borrow::ToOwned, string::String};
+
+pub(crate) const TAG_LEN: usize = 128;
+
+/// An ID3v1 tag found at the end of a stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Id3v1Tag {
+    /// Title of the track.
+    pub title: String,
+    /// Artist of the track.
+    pub artist: String,
+    /// Album the track belongs to.
+    pub album: String,
+    /// Release year.
+    pub year: String,
+    /// Free form comment.
+    pub comment: String,
+    /// Track number, only stored by ID3v1.1 tags.
+    pub track: Option<u8>,
+    /// Index of the genre in the ID3v1 genre list, 255 when unset.
+    pub genre: u8,
+}
+
+/// Parses the 128 byte ID3v1 tag held in `data`, if `data` holds one.
+pub(crate) fn parse(data: &[u8]) -> Option<Id3v1Tag> {
+    if data.len() != TAG_LEN || !data.starts_with(b"TAG") {
+        return None;
+    }
+
+    let comment = &data[97..127];
+    // ID3v1.1 steals the last two bytes of the comment for the track number.
+    let (comment, track) = if comment[28] == 0 && comment[29] != 0 {
+        (&comment[..28], Some(comment[29]))
+    } else {
+        (comment, None)
+    };
+
+    Some(Id3v1Tag {
+        title: decode_text(&data[3..33]),
+        artist: decode_text(&data[33..63]),
+        album: decode_text(&data[63..93]),
+        year: decode_text(&data[93..97]),
+        comment: decode_text(comment),
+        track,
+        genre: data[127],
+    })
+}
+
+// ID3v1 fields are ISO-8859-1, padded with zeroes or spaces.
+fn decode_text(data: &[u8]) -> String {
+    let data = match data.iter().position(|&b| b == 0) {
+        Some(end) => &data[..end],
+        None => data,
+    };
+
+    let text: String = data.iter().map(|&b| b as char).collect();
+    text.trim_end().to_owned()
+}
@@ -0,0 +1,476 @@
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+pub(crate) const HEADER_LEN: usize = 10;
+
+const FLAG_UNSYNCHRONISATION: u8 = 0x80;
+const FLAG_EXTENDED_HEADER: u8 = 0x40;
+const FLAG_FOOTER: u8 = 0x10;
+
+/// An ID3v2 tag found at the start of a stream.
+#[derive(Debug, Clone)]
+pub struct Id3v2Tag {
+    /// Major version of the tag (2, 3 or 4).
+    pub version: u8,
+    /// Revision of the tag.
+    pub revision: u8,
+    /// The frames of the tag that were understood, in the order they appear.
+    pub frames: Vec<Id3v2Frame>,
+}
+
+/// A frame of an ID3v2 tag. ID3v2.2 frames are reported using their ID3v2.3
+/// identifiers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Id3v2Frame {
+    /// A text information frame, such as `TIT2` (title), `TPE1` (artist) or
+    /// `TALB` (album).
+    Text {
+        /// Frame identifier.
+        id: String,
+        /// The text, with multiple values separated by `/`.
+        text: String,
+    },
+    /// A user defined text frame (`TXXX`).
+    UserText {
+        /// Description of the value.
+        description: String,
+        /// The value.
+        value: String,
+    },
+    /// A comment frame (`COMM`).
+    Comment {
+        /// ISO-639-2 language code.
+        language: String,
+        /// Short description of the comment.
+        description: String,
+        /// The comment.
+        text: String,
+    },
+    /// An attached picture frame (`APIC`).
+    Picture {
+        /// MIME type of the image.
+        mime_type: String,
+        /// The kind of picture, such as 3 for the front cover.
+        picture_type: u8,
+        /// Description of the picture.
+        description: String,
+        /// The image data.
+        data: Vec<u8>,
+    },
+}
+
+impl Id3v2Tag {
+    /// Return the value of the first text frame with the given identifier.
+    pub fn text(&self, id: &str) -> Option<&str> {
+        self.frames.iter().find_map(|frame| match frame {
+            Id3v2Frame::Text { id: frame_id, text } if frame_id == id => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Return the title (`TIT2`).
+    pub fn title(&self) -> Option<&str> {
+        self.text("TIT2")
+    }
+
+    /// Return the lead artist (`TPE1`).
+    pub fn artist(&self) -> Option<&str> {
+        self.text("TPE1")
+    }
+
+    /// Return the album (`TALB`).
+    pub fn album(&self) -> Option<&str> {
+        self.text("TALB")
+    }
+}
+
+/// Returns the total size of the ID3v2 tag at the start of `data`, including
+/// its header and footer, or `None` if `data` doesn't start with one.
+pub(crate) fn tag_len(data: &[u8]) -> Option<usize> {
+    if data.len() < HEADER_LEN || !data.starts_with(b"ID3") || data[3] == 0xff || data[4] == 0xff {
+        return None;
+    }
+
+    let size = syncsafe(&data[6..10])?;
+    let footer = if data[5] & FLAG_FOOTER != 0 {
+        HEADER_LEN
+    } else {
+        0
+    };
+
+    Some(HEADER_LEN + size + footer)
+}
+
+/// Parses the ID3v2 tag held in `data`, as delimited by [`tag_len`].
+pub(crate) fn parse(data: &[u8]) -> Option<Id3v2Tag> {
+    let size = syncsafe(data.get(6..10)?)?;
+    let version = data[3];
+    let flags = data[5];
+    let mut tag = Id3v2Tag {
+        version,
+        revision: data[4],
+        frames: Vec::new(),
+    };
+
+    let mut body = data.get(HEADER_LEN..HEADER_LEN + size)?.to_vec();
+    // Before ID3v2.4 unsynchronisation is applied to the whole tag at once.
+    if version < 4 && flags & FLAG_UNSYNCHRONISATION != 0 {
+        body = resynchronise(&body);
+    }
+
+    let mut frames = &body[..];
+    if version >= 3 && flags & FLAG_EXTENDED_HEADER != 0 {
+        let len = if version == 3 {
+            be(frames.get(..4)?) + 4
+        } else {
+            syncsafe(frames.get(..4)?)?
+        };
+        frames = frames.get(len..)?;
+    } else if version == 2 && flags & FLAG_EXTENDED_HEADER != 0 {
+        // Compressed ID3v2.2 tags were never given a compression scheme.
+        return Some(tag);
+    }
+
+    while let Some((id, content)) = next_frame(&mut frames, version) {
+        if let Some(frame) = parse_frame(&id, &content, version) {
+            tag.frames.push(frame);
+        }
+    }
+
+    Some(tag)
+}
+
+// Splits the next frame off `data`, returning its (ID3v2.3) identifier and its
+// decoded content.
+fn next_frame(data: &mut &[u8], version: u8) -> Option<(String, Vec<u8>)> {
+    let (id, size, flags, header_len) = if version == 2 {
+        let header = data.get(..6)?;
+        let id = match &header[..3] {
+            b"TXX" => "TXXX".to_owned(),
+            b"COM" => "COMM".to_owned(),
+            b"PIC" => "APIC".to_owned(),
+            b"TT2" => "TIT2".to_owned(),
+            b"TP1" => "TPE1".to_owned(),
+            b"TAL" => "TALB".to_owned(),
+            id => String::from_utf8_lossy(id).into_owned(),
+        };
+        (id, be(&header[3..6]), [0, 0], 6)
+    } else {
+        let header = data.get(..10)?;
+        let size = if version == 4 {
+            syncsafe(&header[4..8])?
+        } else {
+            be(&header[4..8])
+        };
+        let id = String::from_utf8_lossy(&header[..4]).into_owned();
+        (id, size, [header[8], header[9]], 10)
+    };
+
+    // Padding, or garbage, after the last frame.
+    if !id
+        .bytes()
+        .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let end = size.checked_add(header_len)?;
+    let mut content = data.get(header_len..end)?;
+    *data = &data[end..];
+
+    let format = flags[1];
+    let (compressed, encrypted, grouped, unsynchronised, data_length) = match version {
+        3 => (
+            format & 0x80 != 0,
+            format & 0x40 != 0,
+            format & 0x20 != 0,
+            false,
+            false,
+        ),
+        4 => (
+            format & 0x08 != 0,
+            format & 0x04 != 0,
+            format & 0x40 != 0,
+            format & 0x02 != 0,
+            format & 0x01 != 0,
+        ),
+        _ => (false, false, false, false, false),
+    };
+
+    if grouped {
+        content = content.get(1..)?;
+    }
+    if data_length || (version == 3 && compressed) {
+        content = content.get(4..)?;
+    }
+    if compressed || encrypted {
+        // Neither is supported, report the frame with no content so that it
+        // is ignored.
+        return Some((id, Vec::new()));
+    }
+
+    let content = if unsynchronised {
+        resynchronise(content)
+    } else {
+        content.to_vec()
+    };
+
+    Some((id, content))
+}
+
+fn parse_frame(id: &str, data: &[u8], version: u8) -> Option<Id3v2Frame> {
+    let (&encoding, data) = data.split_first()?;
+
+    match id {
+        "TXXX" => {
+            let (description, value) = split_terminated(encoding, data);
+            Some(Id3v2Frame::UserText {
+                description: decode_text(encoding, description),
+                value: decode_text(encoding, value),
+            })
+        }
+        _ if id.starts_with('T') => Some(Id3v2Frame::Text {
+            id: id.to_owned(),
+            text: decode_text(encoding, data),
+        }),
+        "COMM" => {
+            let language = String::from_utf8_lossy(data.get(..3)?).into_owned();
+            let (description, text) = split_terminated(encoding, &data[3..]);
+            Some(Id3v2Frame::Comment {
+                language,
+                description: decode_text(encoding, description),
+                text: decode_text(encoding, text),
+            })
+        }
+        "APIC" => {
+            // ID3v2.2 pictures have a three letter image format instead of a
+            // MIME type.
+            let (mime_type, data) = if version == 2 {
+                let format = String::from_utf8_lossy(data.get(..3)?).to_ascii_lowercase();
+                (
+                    format!("image/{}", format.replace("jpg", "jpeg")),
+                    &data[3..],
+                )
+            } else {
+                let (mime_type, data) = split_terminated(0, data);
+                (decode_text(0, mime_type), data)
+            };
+            let (&picture_type, data) = data.split_first()?;
+            let (description, data) = split_terminated(encoding, data);
+
+            Some(Id3v2Frame::Picture {
+                mime_type,
+                picture_type,
+                description: decode_text(encoding, description),
+                data: data.to_vec(),
+            })
+        }
+        _ => None,
+    }
+}
+
+// Splits `data` at the first string terminator of the given text encoding.
+fn split_terminated(encoding: u8, data: &[u8]) -> (&[u8], &[u8]) {
+    let position = if encoding == 1 || encoding == 2 {
+        data.chunks_exact(2)
+            .position(|c| c == [0, 0])
+            .map(|i| (i * 2, 2))
+    } else {
+        data.iter().position(|&b| b == 0).map(|i| (i, 1))
+    };
+
+    match position {
+        Some((i, len)) => (&data[..i], &data[i + len..]),
+        None => (data, &[]),
+    }
+}
+
+fn decode_text(encoding: u8, data: &[u8]) -> String {
+    let text = match encoding {
+        0 => data.iter().map(|&b| b as char).collect(),
+        1 | 2 => {
+            let mut big_endian = encoding == 2;
+            let mut data = data;
+            if data.starts_with(&[0xfe, 0xff]) {
+                big_endian = true;
+                data = &data[2..];
+            } else if data.starts_with(&[0xff, 0xfe]) {
+                big_endian = false;
+                data = &data[2..];
+            }
+            let units: Vec<u16> = data
+                .chunks_exact(2)
+                .map(|c| {
+                    if big_endian {
+                        u16::from_be_bytes([c[0], c[1]])
+                    } else {
+                        u16::from_le_bytes([c[0], c[1]])
+                    }
+                })
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => String::from_utf8_lossy(data).into_owned(),
+    };
+
+    // ID3v2.4 separates multiple values with terminators.
+    text.trim_end_matches('\0').replace('\0', "/")
+}
+
+// Undoes unsynchronisation, which inserts a zero byte after every 0xff.
+fn resynchronise(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut previous = 0;
+    for &b in data {
+        if !(previous == 0xff && b == 0) {
+            out.push(b);
+        }
+        previous = b;
+    }
+    out
+}
+
+fn syncsafe(bytes: &[u8]) -> Option<usize> {
+    bytes.iter().try_fold(0, |acc, &b| {
+        if b & 0x80 == 0 {
+            Some((acc << 7) | b as usize)
+        } else {
+            None
+        }
+    })
+}
+
+fn be(bytes: &[u8]) -> usize {
+    bytes.iter().fold(0, |acc, &b| (acc << 8) | b as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    // A tag of the given version holding `body`, with a syncsafe size.
+    fn tag(version: u8, flags: u8, body: &[u8]) -> Vec<u8> {
+        let size = body.len();
+        let mut data = vec![b'I', b'D', b'3', version, 0, flags];
+        data.extend((0..4).rev().map(|i| (size >> (i * 7)) as u8 & 0x7f));
+        data.extend_from_slice(body);
+        data
+    }
+
+    // A text frame of a ID3v2.3 tag.
+    fn frame(id: &[u8; 4], text: &str) -> Vec<u8> {
+        let mut data = id.to_vec();
+        data.extend_from_slice(&(text.len() as u32 + 1).to_be_bytes());
+        data.extend_from_slice(&[0, 0, 0]);
+        data.extend_from_slice(text.as_bytes());
+        data
+    }
+
+    #[test]
+    fn tag_len() {
+        let data = tag(3, 0, &[0; 200]);
+        assert_eq!(super::tag_len(&data), Some(210));
+        assert_eq!(super::tag_len(&tag(4, FLAG_FOOTER, &[0; 200])), Some(220));
+        assert_eq!(super::tag_len(&data[..9]), None);
+        assert_eq!(super::tag_len(b"TAG0123456"), None);
+
+        // Sizes are syncsafe
+        let mut data = data;
+        data[9] = 0x80;
+        assert_eq!(super::tag_len(&data), None);
+    }
+
+    #[test]
+    fn text_frames() {
+        let mut body = frame(b"TIT2", "Title");
+        body.extend(frame(b"TPE1", "Artist"));
+        body.extend_from_slice(&[0; 16]);
+
+        let tag = parse(&tag(3, 0, &body)).unwrap();
+        assert_eq!(tag.version, 3);
+        assert_eq!(tag.title(), Some("Title"));
+        assert_eq!(tag.artist(), Some("Artist"));
+        assert_eq!(tag.frames.len(), 2);
+    }
+
+    #[test]
+    fn resynchronise() {
+        assert_eq!(super::resynchronise(&[0xff, 0x00, 0xe0]), [0xff, 0xe0]);
+        assert_eq!(super::resynchronise(&[0xff, 0x00, 0x00]), [0xff, 0x00]);
+        assert_eq!(
+            super::resynchronise(&[0xff, 0x00, 0xff, 0x00, 0x01]),
+            [0xff, 0xff, 0x01]
+        );
+        assert_eq!(
+            super::resynchronise(&[0x00, 0xfe, 0x00]),
+            [0x00, 0xfe, 0x00]
+        );
+    }
+
+    #[test]
+    fn unsynchronised_tag() {
+        // A Latin-1 "\u{ff}", followed by the zero byte inserted after it
+        let mut body = b"TIT2\0\0\0\x02\0\0".to_vec();
+        body.extend_from_slice(&[0x00, 0xff, 0x00]);
+
+        let tag = parse(&tag(3, FLAG_UNSYNCHRONISATION, &body)).unwrap();
+        assert_eq!(tag.title(), Some("\u{ff}"));
+    }
+
+    #[test]
+    fn extended_header() {
+        // The ID3v2.3 size excludes itself, the ID3v2.4 one doesn't and is
+        // syncsafe.
+        let mut v3 = vec![0, 0, 0, 6, 0, 0, 0, 0, 0, 0];
+        v3.extend(frame(b"TIT2", "Title"));
+        let tag3 = parse(&tag(3, FLAG_EXTENDED_HEADER, &v3)).unwrap();
+        assert_eq!(tag3.title(), Some("Title"));
+
+        let mut v4 = vec![0, 0, 0, 6, 1, 0];
+        v4.extend(frame(b"TIT2", "Title"));
+        let tag4 = parse(&tag(4, FLAG_EXTENDED_HEADER, &v4)).unwrap();
+        assert_eq!(tag4.title(), Some("Title"));
+
+        // Compressed ID3v2.2 tags are skipped
+        let tag2 = parse(&tag(2, FLAG_EXTENDED_HEADER, &[0; 16])).unwrap();
+        assert!(tag2.frames.is_empty());
+    }
+
+    #[test]
+    fn v2_2_ids() {
+        let mut body = Vec::new();
+        for (id, text) in [(b"TT2", "Title"), (b"TP1", "Artist"), (b"TAL", "Album")] {
+            body.extend_from_slice(id);
+            body.extend_from_slice(&[0, 0, text.len() as u8 + 1, 0]);
+            body.extend_from_slice(text.as_bytes());
+        }
+        body.extend_from_slice(b"PIC");
+        body.extend_from_slice(&[0, 0, 9, 0]);
+        body.extend_from_slice(b"JPG\x03\0\xff\xd8\xff");
+        body.extend_from_slice(b"COM");
+        body.extend_from_slice(&[0, 0, 8, 0]);
+        body.extend_from_slice(b"eng\0Hey");
+
+        let tag = parse(&tag(2, 0, &body)).unwrap();
+        assert_eq!(tag.title(), Some("Title"));
+        assert_eq!(tag.artist(), Some("Artist"));
+        assert_eq!(tag.album(), Some("Album"));
+        assert_eq!(
+            tag.frames[3],
+            Id3v2Frame::Picture {
+                mime_type: "image/jpeg".to_owned(),
+                picture_type: 3,
+                description: String::new(),
+                data: vec![0xff, 0xd8, 0xff],
+            }
+        );
+        assert_eq!(
+            tag.frames[4],
+            Id3v2Frame::Comment {
+                language: "eng".to_owned(),
+                description: String::new(),
+                text: "Hey".to_owned(),
+            }
+        );
+    }
+}
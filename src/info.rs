@@ -0,0 +1,474 @@
+use crate::header::{FrameHeader, Layer, HEADER_LEN};
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+use core::{convert::TryInto, time::Duration};
+
+const XING_FLAG_FRAMES: u32 = 0x1;
+const XING_FLAG_BYTES: u32 = 0x2;
+const XING_FLAG_TOC: u32 = 0x4;
+const XING_FLAG_QUALITY: u32 = 0x8;
+
+// Size of the LAME extension following the Xing/Info fields.
+const LAME_TAG_LEN: usize = 36;
+// The VBRI tag is always found 32 bytes after the frame header.
+const VBRI_OFFSET: usize = HEADER_LEN + 32;
+
+/// Information about a whole MP3 stream, read from the VBR tag (Xing, Info or
+/// VBRI) that some encoders write in place of the first frame, or gathered by
+/// `probe`.
+#[derive(Debug, Clone)]
+pub struct StreamInfo {
+    /// The kind of VBR tag found in the stream, if any.
+    pub tag: Option<VbrTag>,
+    /// Sample rate of the stream in hertz.
+    pub sample_rate: i32,
+    /// The number of channels in the stream.
+    pub channels: usize,
+    /// MPEG layer used by the stream.
+    pub layer: usize,
+    /// The number of samples (per channel) in each frame.
+    pub samples_per_frame: usize,
+    /// The number of audio frames in the stream, not counting the tag frame.
+    pub frames: Option<u32>,
+    /// The size of the stream in bytes.
+    pub bytes: Option<u32>,
+    /// Table of contents, used to estimate byte positions when seeking.
+    pub toc: Option<Toc>,
+    /// VBR quality indicator, from 0 (best) to 100 (worst).
+    pub quality: Option<u32>,
+    /// The LAME extension of a Xing or Info tag, if present.
+    pub lame: Option<LameTag>,
+    /// Whether the bitrate is constant or variable, if known.
+    pub bitrate_mode: Option<BitrateMode>,
+    /// Average bitrate in kb/s, if known.
+    pub bitrate: Option<u32>,
+}
+
+/// Whether the bitrate of a stream is constant or variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitrateMode {
+    /// Constant bitrate (CBR).
+    Constant,
+    /// Variable bitrate (VBR or ABR).
+    Variable,
+}
+
+/// The kind of VBR tag found in the first frame of a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VbrTag {
+    /// A Xing tag, written by encoders for VBR streams.
+    Xing,
+    /// An Info tag, the Xing tag variant written by LAME for CBR streams.
+    Info,
+    /// A VBRI tag, written by the Fraunhofer encoder.
+    Vbri,
+}
+
+/// A seek table mapping positions in time to positions in the stream.
+#[derive(Debug, Clone)]
+pub enum Toc {
+    /// 100 entries, where entry `i` is the byte position at `i` percent of the
+    /// duration of the stream, expressed in 1/256ths of the stream size.
+    Xing(Box<[u8; 100]>),
+    /// The size in bytes of consecutive runs of `frames_per_entry` frames.
+    Vbri {
+        /// The number of frames covered by each entry.
+        frames_per_entry: u32,
+        /// Size in bytes of each run of frames.
+        entries: Vec<u32>,
+    },
+}
+
+/// The LAME extension of a Xing or Info tag.
+#[derive(Debug, Clone)]
+pub struct LameTag {
+    /// Short encoder version string, such as `LAME3.100`.
+    pub encoder: String,
+    /// Tag revision.
+    pub revision: u8,
+    /// VBR method used by the encoder.
+    pub vbr_method: u8,
+    /// Lowpass filter frequency in hertz.
+    pub lowpass: u32,
+    /// Peak signal amplitude, where 1.0 is full scale.
+    pub peak: Option<f32>,
+    /// Track (radio) replay gain.
+    pub track_gain: Option<ReplayGain>,
+    /// Album (audiophile) replay gain.
+    pub album_gain: Option<ReplayGain>,
+    /// Samples (per channel) of silence added by the encoder at the start of
+    /// the stream.
+    pub encoder_delay: u16,
+    /// Samples (per channel) of silence added by the encoder at the end of the
+    /// stream.
+    pub encoder_padding: u16,
+}
+
+/// A replay gain adjustment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayGain {
+    /// Gain adjustment in decibels.
+    pub gain: f32,
+    /// Who set the gain: 1 for the artist, 2 for the user, 3 for the model
+    /// and 4 for a simple RMS average.
+    pub originator: u8,
+}
+
+impl StreamInfo {
+    /// The total number of samples (per channel) in the stream, before any
+    /// encoder delay or padding is removed.
+    pub fn total_samples(&self) -> Option<u64> {
+        self.frames
+            .map(|frames| u64::from(frames) * self.samples_per_frame as u64)
+    }
+
+    /// The duration of the stream.
+    pub fn duration(&self) -> Option<Duration> {
+        let samples = self.total_samples()?;
+        let sample_rate = self.sample_rate as u64;
+        if sample_rate == 0 {
+            return None;
+        }
+
+        Some(Duration::new(
+            samples / sample_rate,
+            ((samples % sample_rate) * 1_000_000_000 / sample_rate) as u32,
+        ))
+    }
+
+    // Average bitrate of `bytes` of audio over the duration of the stream.
+    pub(crate) fn average_bitrate(&self, bytes: u64) -> Option<u32> {
+        let samples = self.total_samples()?;
+        if samples == 0 {
+            return None;
+        }
+
+        Some((bytes * 8 * self.sample_rate as u64 / samples / 1000) as u32)
+    }
+}
+
+/// Parses the VBR tag of a layer III `frame`, if any.
+pub(crate) fn parse(frame: &[u8]) -> Option<StreamInfo> {
+    let header = FrameHeader::parse(frame.get(..HEADER_LEN)?.try_into().ok()?)?;
+    if header.layer != Layer::Layer3 {
+        return None;
+    }
+
+    let mut info = StreamInfo {
+        tag: None,
+        sample_rate: header.sample_rate as i32,
+        channels: header.channels(),
+        layer: 3,
+        samples_per_frame: header.samples_per_frame(),
+        frames: None,
+        bytes: None,
+        toc: None,
+        quality: None,
+        lame: None,
+        bitrate_mode: None,
+        bitrate: None,
+    };
+
+    let side_info = header.side_info_len() + if header.crc { 2 } else { 0 };
+    let xing = &frame[(HEADER_LEN + side_info).min(frame.len())..];
+    if xing.starts_with(b"Xing") || xing.starts_with(b"Info") {
+        let (tag, bitrate_mode) = if xing.starts_with(b"Info") {
+            (VbrTag::Info, BitrateMode::Constant)
+        } else {
+            (VbrTag::Xing, BitrateMode::Variable)
+        };
+        info.tag = Some(tag);
+        info.bitrate_mode = Some(bitrate_mode);
+        parse_xing(&xing[4..], &mut info)?;
+    } else if frame.len() > VBRI_OFFSET && frame[VBRI_OFFSET..].starts_with(b"VBRI") {
+        info.tag = Some(VbrTag::Vbri);
+        info.bitrate_mode = Some(BitrateMode::Variable);
+        parse_vbri(&frame[VBRI_OFFSET + 4..], &mut info)?;
+    } else {
+        return None;
+    }
+
+    if let Some(bytes) = info.bytes {
+        info.bitrate = info.average_bitrate(u64::from(bytes));
+    }
+
+    Some(info)
+}
+
+fn parse_xing(mut data: &[u8], info: &mut StreamInfo) -> Option<()> {
+    let flags = read_u32(&mut data)?;
+    if flags & XING_FLAG_FRAMES != 0 {
+        info.frames = Some(read_u32(&mut data)?);
+    }
+    if flags & XING_FLAG_BYTES != 0 {
+        info.bytes = Some(read_u32(&mut data)?);
+    }
+    if flags & XING_FLAG_TOC != 0 {
+        let mut toc = Box::new([0; 100]);
+        toc.copy_from_slice(data.get(..100)?);
+        info.toc = Some(Toc::Xing(toc));
+        data = &data[100..];
+    }
+    if flags & XING_FLAG_QUALITY != 0 {
+        info.quality = Some(read_u32(&mut data)?);
+    }
+
+    // Encoders other than LAME (such as Lavc) write the same extension, so it
+    // is identified by a non-empty encoder string rather than by its name.
+    if data.len() >= LAME_TAG_LEN && data[0] != 0 {
+        info.lame = Some(parse_lame(data));
+    }
+
+    Some(())
+}
+
+fn parse_lame(data: &[u8]) -> LameTag {
+    let encoder = String::from_utf8_lossy(&data[..9])
+        .trim_end_matches(|c: char| c == '\0' || c.is_whitespace())
+        .to_owned();
+    let peak = u32::from_be_bytes([data[11], data[12], data[13], data[14]]);
+
+    LameTag {
+        encoder,
+        revision: data[9] >> 4,
+        vbr_method: data[9] & 0x0f,
+        lowpass: u32::from(data[10]) * 100,
+        peak: if peak == 0 {
+            None
+        } else {
+            Some(peak as f32 / (1 << 23) as f32)
+        },
+        track_gain: parse_replay_gain(u16::from_be_bytes([data[15], data[16]]), 1),
+        album_gain: parse_replay_gain(u16::from_be_bytes([data[17], data[18]]), 2),
+        encoder_delay: (u16::from(data[21]) << 4) | (u16::from(data[22]) >> 4),
+        encoder_padding: (u16::from(data[22] & 0x0f) << 8) | u16::from(data[23]),
+    }
+}
+
+fn parse_replay_gain(field: u16, name: u16) -> Option<ReplayGain> {
+    if field >> 13 != name {
+        return None;
+    }
+
+    let gain = f32::from(field & 0x1ff) / 10.0;
+    Some(ReplayGain {
+        gain: if field & 0x200 != 0 { -gain } else { gain },
+        originator: ((field >> 10) & 0x7) as u8,
+    })
+}
+
+fn parse_vbri(mut data: &[u8], info: &mut StreamInfo) -> Option<()> {
+    let _version = read_u16(&mut data)?;
+    let _delay = read_u16(&mut data)?;
+    info.quality = Some(u32::from(read_u16(&mut data)?));
+    info.bytes = Some(read_u32(&mut data)?);
+    info.frames = Some(read_u32(&mut data)?);
+
+    let entries = read_u16(&mut data)? as usize;
+    let scale = u32::from(read_u16(&mut data)?);
+    let entry_size = read_u16(&mut data)? as usize;
+    let frames_per_entry = u32::from(read_u16(&mut data)?);
+
+    if (1..=4).contains(&entry_size) && data.len() >= entries * entry_size {
+        // A TOC whose scaled entries overflow is dropped.
+        let entries = data
+            .chunks_exact(entry_size)
+            .take(entries)
+            .map(|entry| {
+                let entry = entry.iter().fold(0, |acc, &b| (acc << 8) | u32::from(b));
+                entry.checked_mul(scale)
+            })
+            .collect::<Option<_>>();
+        info.toc = entries.map(|entries| Toc::Vbri {
+            frames_per_entry,
+            entries,
+        });
+    }
+
+    Some(())
+}
+
+fn read_u16(data: &mut &[u8]) -> Option<u16> {
+    let bytes = data.get(..2)?;
+    let value = u16::from_be_bytes([bytes[0], bytes[1]]);
+    *data = &data[2..];
+    Some(value)
+}
+
+fn read_u32(data: &mut &[u8]) -> Option<u32> {
+    let bytes = data.get(..4)?;
+    let value = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    *data = &data[4..];
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn lame_tag() -> [u8; LAME_TAG_LEN] {
+        let mut data = [0; LAME_TAG_LEN];
+        data[..9].copy_from_slice(b"LAME3.100");
+        // Revision 1, VBR method 3
+        data[9] = 0x13;
+        // 19.5 kHz lowpass, rounded down
+        data[10] = 195;
+        // Peak of 1.0
+        data[11..15].copy_from_slice(&[0x00, 0x80, 0x00, 0x00]);
+        // Track gain of -6.5 dB, set by the model
+        data[15..17].copy_from_slice(&[0x2e, 0x41]);
+        // Album gain of +1.2 dB, set by the user
+        data[17..19].copy_from_slice(&[0x48, 0x0c]);
+        // 576 samples of delay, 1234 of padding
+        data[21..24].copy_from_slice(&[0x24, 0x04, 0xd2]);
+        data
+    }
+
+    #[test]
+    fn lame() {
+        let lame = parse_lame(&lame_tag());
+        assert_eq!(lame.encoder, "LAME3.100");
+        assert_eq!(lame.revision, 1);
+        assert_eq!(lame.vbr_method, 3);
+        assert_eq!(lame.lowpass, 19500);
+        assert_eq!(lame.peak, Some(1.0));
+        assert_eq!(
+            lame.track_gain,
+            Some(ReplayGain {
+                gain: -6.5,
+                originator: 3,
+            })
+        );
+        assert_eq!(
+            lame.album_gain,
+            Some(ReplayGain {
+                gain: 1.2,
+                originator: 2,
+            })
+        );
+        assert_eq!(lame.encoder_delay, 576);
+        assert_eq!(lame.encoder_padding, 1234);
+    }
+
+    #[test]
+    fn lame_without_peak_nor_gain() {
+        let mut data = lame_tag();
+        data[9..19].iter_mut().for_each(|b| *b = 0);
+        data[..9].copy_from_slice(b"Lavc58.13");
+
+        let lame = parse_lame(&data);
+        assert_eq!(lame.encoder, "Lavc58.13");
+        assert_eq!(lame.peak, None);
+        assert_eq!(lame.track_gain, None);
+        assert_eq!(lame.album_gain, None);
+    }
+
+    #[test]
+    fn replay_gain() {
+        // The name has to be the expected one
+        assert_eq!(parse_replay_gain(0x2e41, 2), None);
+        assert_eq!(parse_replay_gain(0x0000, 1), None);
+        assert_eq!(
+            parse_replay_gain(0x2c00 | 0x1ff, 1),
+            Some(ReplayGain {
+                gain: 51.1,
+                originator: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn xing() {
+        // MPEG 1 layer III, 128 kb/s, 44.1 kHz, stereo
+        let mut frame = vec![0; 417];
+        frame[..4].copy_from_slice(&[0xff, 0xfb, 0x90, 0x00]);
+        let mut tag = &mut frame[HEADER_LEN + 32..];
+        for field in [
+            &b"Xing"[..],
+            &0xfu32.to_be_bytes(),
+            &1000u32.to_be_bytes(),
+            &500_000u32.to_be_bytes(),
+            &[7; 100],
+            &50u32.to_be_bytes(),
+            &lame_tag(),
+        ] {
+            tag[..field.len()].copy_from_slice(field);
+            tag = &mut tag[field.len()..];
+        }
+
+        let info = parse(&frame).unwrap();
+        assert_eq!(info.tag, Some(VbrTag::Xing));
+        assert_eq!(info.bitrate_mode, Some(BitrateMode::Variable));
+        assert_eq!(info.sample_rate, 44100);
+        assert_eq!(info.channels, 2);
+        assert_eq!(info.frames, Some(1000));
+        assert_eq!(info.total_samples(), Some(1_152_000));
+        assert_eq!(info.bytes, Some(500_000));
+        assert!(matches!(info.toc, Some(Toc::Xing(toc)) if toc[99] == 7));
+        assert_eq!(info.quality, Some(50));
+        assert_eq!(info.lame.unwrap().encoder_delay, 576);
+        // 500 kB over 26.12 seconds
+        assert_eq!(info.bitrate, Some(153));
+
+        frame[HEADER_LEN + 32..][..4].copy_from_slice(b"Info");
+        let info = parse(&frame).unwrap();
+        assert_eq!(info.tag, Some(VbrTag::Info));
+        assert_eq!(info.bitrate_mode, Some(BitrateMode::Constant));
+
+        frame[HEADER_LEN + 32..][..4].copy_from_slice(b"Junk");
+        assert!(parse(&frame).is_none());
+    }
+
+    fn vbri_frame(scale: u16) -> Vec<u8> {
+        // MPEG 1 layer III, 128 kb/s, 44.1 kHz, stereo
+        let mut frame = vec![0; 417];
+        frame[..4].copy_from_slice(&[0xff, 0xfb, 0x90, 0x00]);
+        let mut tag = &mut frame[VBRI_OFFSET..];
+        for field in [
+            &b"VBRI"[..],
+            &1u16.to_be_bytes(),
+            &0u16.to_be_bytes(),
+            &75u16.to_be_bytes(),
+            &500_000u32.to_be_bytes(),
+            &1000u32.to_be_bytes(),
+            // Two entries of 2 bytes, for 500 frames each
+            &2u16.to_be_bytes(),
+            &scale.to_be_bytes(),
+            &2u16.to_be_bytes(),
+            &500u16.to_be_bytes(),
+            &[0x01, 0x00, 0xff, 0xff],
+        ] {
+            tag[..field.len()].copy_from_slice(field);
+            tag = &mut tag[field.len()..];
+        }
+        frame
+    }
+
+    #[test]
+    fn vbri() {
+        let info = parse(&vbri_frame(2)).unwrap();
+        assert_eq!(info.tag, Some(VbrTag::Vbri));
+        assert_eq!(info.bitrate_mode, Some(BitrateMode::Variable));
+        assert_eq!(info.quality, Some(75));
+        assert_eq!(info.frames, Some(1000));
+        assert_eq!(info.bytes, Some(500_000));
+        assert!(matches!(
+            info.toc,
+            Some(Toc::Vbri { frames_per_entry: 500, entries }) if entries == [0x200, 0x1fffe]
+        ));
+    }
+
+    #[test]
+    fn vbri_toc_overflow() {
+        let info = parse(&vbri_frame(u16::MAX)).unwrap();
+        assert!(info.toc.is_some());
+
+        // 0xffff * 0x10002 overflows
+        let mut frame = vbri_frame(u16::MAX);
+        frame[VBRI_OFFSET + 22..][..2].copy_from_slice(&[4, 0]);
+        frame[VBRI_OFFSET + 26..][..8].copy_from_slice(&[0, 0, 0, 1, 0, 1, 0, 2]);
+        let info = parse(&frame).unwrap();
+        assert!(info.toc.is_none());
+        assert_eq!(info.frames, Some(1000));
+    }
+}
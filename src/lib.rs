@@ -7,63 +7,247 @@
 //! By enabling the feature flag `async_tokio` you can decode frames using async
 //! IO and tokio.
 //!
+//! ## Other async runtimes
+//!
+//! The feature flag `async_futures` does the same for readers implementing
+//! `futures_io::AsyncRead`, such as the ones of async-std and smol. See
+//! `AsyncSource`.
+//!
+//! ## Float output
+//!
+//! By enabling the feature flag `float_output` the decoder produces `f32`
+//! samples instead of `i16`. See [`Sample`].
+//!
+//! Beware that this feature isn't additive: minimp3 is built once for the
+//! whole dependency graph, so if any crate in it enables `float_output`,
+//! [`Sample`] is `f32` for every other crate using minimp3 as well, and code
+//! written for `i16` samples stops compiling. Libraries should leave the
+//! choice to the final application, and can stay independent of it by only
+//! naming the [`Sample`] type.
+//!
+//! ## Build options
+//!
+//! The other compile-time options of minimp3 are exposed as feature flags too:
+//!
+//! - `only_mp3`: only decode layer III. Layer I and II frames are skipped, as
+//!   if they were garbage, so the `layer` of decoded frames is always 3.
+//! - `only_simd`: assume SIMD support instead of detecting it at runtime.
+//! - `no_simd`: never use SIMD.
+//! - `nonstandard_but_logical`: decode some nonstandard streams the way other
+//!   decoders do.
+//!
+//! ## `no_std`
+//!
+//! Disabling the default `std` feature makes the crate `no_std`, for embedded
+//! and `wasm32-unknown-unknown` targets. Only `alloc` is needed then, for
+//! [`DecoderCore`], [`PushDecoder`], [`SliceDecoder`] and the tag types;
+//! decoding with [`DecoderCore::decode_into`] or
+//! [`SliceDecoder::next_frame_into`] doesn't allocate. `Decoder` and
+//! everything else reading from `std::io` readers need `std`.
+//!
 //! [See the README for example usages.](https://github.com/germangb/minimp3-rs/tree/async)
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub use ape::{ApeItem, ApeTag, ApeValue};
+#[cfg(any(feature = "async_tokio", feature = "async_futures"))]
+pub use async_io::AsyncSource;
+#[cfg(feature = "async_futures")]
+pub use async_io::FuturesIo;
+#[cfg(feature = "async_tokio")]
+pub use async_io::TokioIo;
+pub use builder::DecoderBuilder;
+pub use decoder_core::{DecodeStats, DecoderCore};
 pub use error::Error;
+#[cfg(feature = "std")]
+pub use frames::Frames;
+pub use header::{ChannelMode, Emphasis, FrameHeader, Layer, Version};
+pub use id3v1::Id3v1Tag;
+pub use id3v2::{Id3v2Frame, Id3v2Tag};
+pub use info::{BitrateMode, LameTag, ReplayGain, StreamInfo, Toc, VbrTag};
+#[cfg(feature = "std")]
+pub use load::{decode_all, decode_file, DecodedAudio};
 pub use minimp3_sys as ffi;
+#[cfg(feature = "std")]
+pub use pcm::PcmReader;
+#[cfg(feature = "std")]
+pub use probe::probe;
+pub use push::PushDecoder;
+pub use slice::SliceDecoder;
 
-use slice_deque::SliceDeque;
-use std::{io, marker::Send, mem};
+use alloc::vec::Vec;
+use core::convert::TryInto;
+#[cfg(feature = "std")]
+use std::{io, mem};
 
+mod ape;
+#[cfg(any(feature = "async_tokio", feature = "async_futures"))]
+mod async_io;
+mod buffer;
+mod builder;
+mod decoder_core;
 mod error;
+#[cfg(feature = "std")]
+pub mod ex;
+#[cfg(feature = "std")]
+mod frames;
+mod gapless;
+mod header;
+mod id3v1;
+mod id3v2;
+mod info;
+#[cfg(feature = "std")]
+mod load;
+#[cfg(feature = "std")]
+mod pcm;
+#[cfg(feature = "std")]
+mod probe;
+mod push;
+#[cfg(feature = "std")]
+mod seek;
+mod slice;
+mod trailers;
 
 /// Maximum number of samples present in a MP3 frame.
 pub const MAX_SAMPLES_PER_FRAME: usize = ffi::MINIMP3_MAX_SAMPLES_PER_FRAME as usize;
 
-const BUFFER_SIZE: usize = MAX_SAMPLES_PER_FRAME * 15;
-const REFILL_TRIGGER: usize = MAX_SAMPLES_PER_FRAME * 8;
+/// The type of the decoded samples: `i16`, or `f32` when the `float_output`
+/// feature is enabled anywhere in the dependency graph (see the crate
+/// documentation).
+pub type Sample = ffi::mp3d_sample_t;
 
 /// A MP3 decoder which consumes a reader and produces [`Frame`]s.
 ///
+/// Its buffering and options can be set up with a [`DecoderBuilder`].
+///
 /// [`Frame`]: ./struct.Frame.html
+#[cfg(feature = "std")]
 pub struct Decoder<R> {
     reader: R,
-    buffer: SliceDeque<u8>,
-    buffer_refill: Box<[u8; MAX_SAMPLES_PER_FRAME * 5]>,
-    decoder: Box<ffi::mp3dec_t>,
+    buffer_refill: Box<[u8]>,
+    core: DecoderCore,
+    // Decoded audio of the last frame, for the methods not given a buffer.
+    pcm: Vec<Sample>,
+    index: Option<seek::SeekIndex>,
+    id3v1: Option<Id3v1Tag>,
+    ape: Option<ApeTag>,
+    // Skips input by seeking the reader, if it can, rather than reading it.
+    seek_input: Option<fn(&mut R, u64) -> io::Result<()>>,
 }
 
-// Explicitly impl [Send] for [Decoder]s. This isn't a great idea and should
-// probably be removed in the future. The only reason it's here is that
-// [SliceDeque] doesn't implement [Send] (since it uses raw pointers
-// internally), even though it's safe to send it across thread boundaries.
-unsafe impl<R: Send> Send for Decoder<R> {}
-
 /// A MP3 frame, owning the decoded audio of that frame.
 #[derive(Debug, Clone)]
 pub struct Frame {
     /// The decoded audio held by this frame. Channels are interleaved.
-    pub data: Vec<i16>,
+    pub data: Vec<Sample>,
     /// This frame's sample rate in hertz.
     pub sample_rate: i32,
     /// The number of channels in this frame.
     pub channels: usize,
-    /// MPEG layer used by this file.
+    /// MPEG layer used by this file. Always 3 with the `only_mp3` feature.
     pub layer: usize,
     /// Current bitrate as of this frame, in kb/s.
     pub bitrate: i32,
+    /// The full header of this frame.
+    pub header: FrameHeader,
+}
+
+/// A MP3 frame, borrowing the decoded audio of that frame from the decoder.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameRef<'a> {
+    /// The decoded audio held by this frame. Channels are interleaved.
+    pub data: &'a [Sample],
+    /// Information about this frame.
+    pub info: FrameInfo,
+}
+
+/// Information about a decoded MP3 frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameInfo {
+    /// The number of samples (per channel) decoded from this frame.
+    pub samples: usize,
+    /// This frame's sample rate in hertz.
+    pub sample_rate: i32,
+    /// The number of channels in this frame.
+    pub channels: usize,
+    /// MPEG layer used by this file. Always 3 with the `only_mp3` feature.
+    pub layer: usize,
+    /// Current bitrate as of this frame, in kb/s.
+    pub bitrate: i32,
+    /// The full header of this frame.
+    pub header: FrameHeader,
+}
+
+impl Frame {
+    fn new(data: Vec<Sample>, info: FrameInfo) -> Self {
+        Self {
+            data,
+            sample_rate: info.sample_rate,
+            channels: info.channels,
+            layer: info.layer,
+            bitrate: info.bitrate,
+            header: info.header,
+        }
+    }
+}
+
+impl FrameInfo {
+    // Builds the information of a frame decoded from `data`.
+    fn new(samples: usize, frame_info: &ffi::mp3dec_frame_info_t, data: &[u8]) -> Self {
+        let offset = frame_info.frame_offset as usize;
+        let header = data[offset..offset + header::HEADER_LEN]
+            .try_into()
+            .ok()
+            .and_then(FrameHeader::parse)
+            .expect("minimp3 only decodes frames with a valid header");
+
+        Self {
+            samples,
+            sample_rate: frame_info.hz,
+            channels: frame_info.channels as usize,
+            layer: frame_info.layer as usize,
+            bitrate: frame_info.bitrate_kbps,
+            header,
+        }
+    }
+}
+
+// Views a buffer of `MAX_SAMPLES_PER_FRAME` samples as an array.
+fn pcm_array(pcm: &mut [Sample]) -> &mut [Sample; MAX_SAMPLES_PER_FRAME] {
+    pcm.try_into()
+        .expect("buffer of MAX_SAMPLES_PER_FRAME samples")
+}
+
+/// Converts `f32` samples to `i16`, clamping them to the `i16` range.
+///
+/// # Panics
+///
+/// Panics if `output` is shorter than `input`.
+#[cfg(feature = "float_output")]
+pub fn f32_to_s16(input: &[f32], output: &mut [i16]) {
+    assert!(output.len() >= input.len());
+    unsafe { ffi::mp3dec_f32_to_s16(input.as_ptr(), output.as_mut_ptr(), input.len() as _) }
 }
 
+#[cfg(feature = "std")]
 impl<R> Decoder<R> {
     /// Creates a new decoder, consuming the `reader`.
     pub fn new(reader: R) -> Self {
-        let mut minidec = unsafe { Box::new(mem::zeroed()) };
-        unsafe { ffi::mp3dec_init(&mut *minidec) }
+        DecoderBuilder::new().build(reader)
+    }
 
+    // Creates a decoder around `core`, reading `read_size` bytes at once.
+    pub(crate) fn with_core(reader: R, core: DecoderCore, read_size: usize) -> Self {
         Self {
             reader,
-            buffer: SliceDeque::with_capacity(BUFFER_SIZE),
-            buffer_refill: Box::new([0; MAX_SAMPLES_PER_FRAME * 5]),
-            decoder: minidec,
+            buffer_refill: vec![0; read_size].into_boxed_slice(),
+            core,
+            pcm: vec![Sample::default(); MAX_SAMPLES_PER_FRAME],
+            index: None,
+            id3v1: None,
+            ape: None,
+            seek_input: None,
         }
     }
 
@@ -83,121 +267,199 @@ impl<R> Decoder<R> {
         self.reader
     }
 
-    fn decode_frame(&mut self) -> Result<Frame, Error> {
-        let mut frame_info = unsafe { mem::zeroed() };
-        let mut pcm = Vec::with_capacity(MAX_SAMPLES_PER_FRAME);
-        let samples: usize = unsafe {
-            ffi::mp3dec_decode_frame(
-                &mut *self.decoder,
-                self.buffer.as_ptr(),
-                self.buffer.len() as _,
-                pcm.as_mut_ptr(),
-                &mut frame_info,
-            ) as _
-        };
+    /// Return the ID3v2 tag found at the start of the stream, if any.
+    ///
+    /// This is only known once the first frame of the stream has been
+    /// located, see [`stream_info`](Decoder::stream_info).
+    pub fn id3v2(&self) -> Option<&Id3v2Tag> {
+        self.core.id3v2()
+    }
 
-        if samples > 0 {
-            unsafe {
-                pcm.set_len(samples * frame_info.channels as usize);
-            }
-        }
+    /// Return the ID3v1 tag found at the end of the stream, if any.
+    ///
+    /// This is only known after calling
+    /// [`read_trailing_tags`](Decoder::read_trailing_tags).
+    pub fn id3v1(&self) -> Option<&Id3v1Tag> {
+        self.id3v1.as_ref()
+    }
 
-        let frame = Frame {
-            data: pcm,
-            sample_rate: frame_info.hz,
-            channels: frame_info.channels as usize,
-            layer: frame_info.layer as usize,
-            bitrate: frame_info.bitrate_kbps,
-        };
+    /// Return the APEv2 tag found at the end of the stream, if any.
+    ///
+    /// This is only known after calling
+    /// [`read_trailing_tags`](Decoder::read_trailing_tags).
+    pub fn ape(&self) -> Option<&ApeTag> {
+        self.ape.as_ref()
+    }
 
-        let current_len = self.buffer.len();
-        self.buffer
-            .truncate_front(current_len - frame_info.frame_bytes as usize);
+    /// Return the information read from the VBR tag (Xing, Info or VBRI) of
+    /// the stream, if it has one.
+    ///
+    /// This is only known once the first frame of the stream has been located,
+    /// either by decoding a frame or by calling
+    /// [`read_stream_info`](Decoder::read_stream_info).
+    pub fn stream_info(&self) -> Option<&StreamInfo> {
+        self.core.stream_info()
+    }
 
-        if samples == 0 {
-            if frame_info.frame_bytes > 0 {
-                Err(Error::SkippedData)
-            } else {
-                Err(Error::InsufficientData)
-            }
-        } else {
-            Ok(frame)
-        }
+    /// Return statistics about the data decoded so far: how many frames were
+    /// decoded or dropped, and how much data was skipped, and where, because
+    /// it was neither a frame nor a tag.
+    pub fn stats(&self) -> &DecodeStats {
+        self.core.stats()
+    }
+
+    /// Enables or disables strict mode, disabled by default.
+    ///
+    /// Data which is neither a frame nor a tag is normally skipped silently
+    /// (and counted in [`stats`](Decoder::stats)). In strict mode, reading a
+    /// frame returns [`Error::Corrupt`] for each run of such data instead,
+    /// once the run ends at the next frame or at the end of the stream, after
+    /// which decoding can go on from that frame.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.core.set_strict(strict);
+    }
+
+    /// Enables or disables the parsing of the ID3v2 tag at the start of the
+    /// stream, enabled by default.
+    ///
+    /// A tag is buffered whole to be parsed, which takes as much memory as the
+    /// tag, cover art included. When disabled, the tag is read through and
+    /// dropped instead, or seeked past with
+    /// [`seek_past_id3v2`](Decoder::seek_past_id3v2), and
+    /// [`id3v2`](Decoder::id3v2) returns `None`. Must be set before the first
+    /// frame is decoded.
+    pub fn set_parse_id3v2(&mut self, enabled: bool) {
+        self.core.set_parse_id3v2(enabled);
     }
 }
 
-#[cfg(feature = "async_tokio")]
-impl<R: tokio::io::AsyncRead + std::marker::Unpin> Decoder<R> {
+#[cfg(feature = "std")]
+impl<R: io::Read> Decoder<R> {
     /// Reads a new frame from the internal reader. Returns a [`Frame`](Frame)
     /// if one was found, or, otherwise, an `Err` explaining why not.
-    pub async fn next_frame_future(&mut self) -> Result<Frame, Error> {
+    ///
+    /// Reads interrupted by a signal are retried. With a non-blocking reader,
+    /// [`Error::WouldBlock`] is returned when no data is available yet, and the
+    /// next call resumes decoding where it stopped.
+    pub fn next_frame(&mut self) -> Result<Frame, Error> {
+        let frame = self.next_frame_ref()?;
+        Ok(Frame::new(frame.data.to_vec(), frame.info))
+    }
+
+    /// Reads a new frame from the internal reader into `pcm`, without
+    /// allocating. Returns the [`FrameInfo`] of the frame, whose decoded audio
+    /// is held in the first `samples * channels` samples of `pcm`, or,
+    /// otherwise, an `Err` explaining why not.
+    pub fn next_frame_into(
+        &mut self,
+        pcm: &mut [Sample; MAX_SAMPLES_PER_FRAME],
+    ) -> Result<FrameInfo, Error> {
+        self.drive(|core| core.decode_into(pcm))
+    }
+
+    /// Reads a new frame from the internal reader, without allocating. Returns
+    /// a [`FrameRef`] borrowing the decoded audio from the decoder if one was
+    /// found, or, otherwise, an `Err` explaining why not.
+    pub fn next_frame_ref(&mut self) -> Result<FrameRef<'_>, Error> {
+        let mut pcm = mem::take(&mut self.pcm);
+        let result = self.next_frame_into(pcm_array(&mut pcm));
+        self.pcm = pcm;
+
+        let info = result?;
+        Ok(FrameRef {
+            data: &self.pcm[..info.samples * info.channels],
+            info,
+        })
+    }
+
+    /// Reads ahead until the first frame of the stream is located, and returns
+    /// the information from its VBR tag (Xing, Info or VBRI) if it has one.
+    /// No audio is decoded.
+    pub fn read_stream_info(&mut self) -> Result<Option<&StreamInfo>, Error> {
+        match self.drive(DecoderCore::read_tags) {
+            Ok(()) | Err(Error::Eof) => Ok(self.core.stream_info()),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Runs `step` on the core, feeding it from the reader for as long as it
+    // asks for more input.
+    fn drive<T>(
+        &mut self,
+        mut step: impl FnMut(&mut DecoderCore) -> Result<T, Error>,
+    ) -> Result<T, Error> {
         loop {
-            // Keep our buffers full
-            let bytes_read = if self.buffer.len() < REFILL_TRIGGER {
-                Some(self.refill_future().await?)
-            } else {
-                None
-            };
-
-            match self.decode_frame() {
-                Ok(frame) => return Ok(frame),
-                // Don't do anything if we didn't have enough data or we skipped data,
-                // just let the loop spin around another time.
-                Err(Error::InsufficientData) | Err(Error::SkippedData) => {
-                    // If there are no more bytes to be read from the file, return EOF
-                    if let Some(0) = bytes_read {
-                        return Err(Error::Eof);
-                    }
-                }
-                Err(e) => return Err(e),
+            match step(&mut self.core) {
+                Err(Error::InsufficientData) => self.refill()?,
+                result => return result,
             }
         }
     }
 
-    async fn refill_future(&mut self) -> Result<usize, io::Error> {
-        use tokio::io::AsyncReadExt;
+    fn refill(&mut self) -> Result<(), Error> {
+        if let (Some(seek_input), true) = (self.seek_input, self.core.discard > 0) {
+            seek_input(&mut self.reader, self.core.discard)?;
+            self.core.offset += self.core.discard;
+            self.core.discard = 0;
+        }
 
-        let read_bytes = self.reader.read(&mut self.buffer_refill[..]).await?;
-        self.buffer.extend(self.buffer_refill[..read_bytes].iter());
+        let len = self.core.input_len(self.buffer_refill.len());
+        let read_bytes = loop {
+            match self.reader.read(&mut self.buffer_refill[..len]) {
+                Ok(read_bytes) => break read_bytes,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Err(Error::WouldBlock),
+                Err(e) => return Err(e.into()),
+            }
+        };
+        if read_bytes == 0 {
+            self.core.finish();
+        } else {
+            self.core.push(&self.buffer_refill[..read_bytes]);
+        }
 
-        Ok(read_bytes)
+        Ok(())
     }
 }
 
-// TODO FIXME do something about the code repetition. The only difference is the
-//  use of .await after IO reads...
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
 
-impl<R: io::Read> Decoder<R> {
-    /// Reads a new frame from the internal reader. Returns a [`Frame`](Frame)
-    /// if one was found, or, otherwise, an `Err` explaining why not.
-    pub fn next_frame(&mut self) -> Result<Frame, Error> {
-        loop {
-            // Keep our buffers full
-            let bytes_read = if self.buffer.len() < REFILL_TRIGGER {
-                Some(self.refill()?)
-            } else {
-                None
-            };
-
-            match self.decode_frame() {
-                Ok(frame) => return Ok(frame),
-                // Don't do anything if we didn't have enough data or we skipped data,
-                // just let the loop spin around another time.
-                Err(Error::InsufficientData) | Err(Error::SkippedData) => {
-                    // If there are no more bytes to be read from the file, return EOF
-                    if let Some(0) = bytes_read {
-                        return Err(Error::Eof);
-                    }
+    // A reader replaying a script of reads.
+    struct Script(VecDeque<io::Result<Vec<u8>>>);
+
+    impl io::Read for Script {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.pop_front() {
+                Some(Ok(data)) => {
+                    buf[..data.len()].copy_from_slice(&data);
+                    Ok(data.len())
                 }
-                Err(e) => return Err(e),
+                Some(Err(e)) => Err(e),
+                None => Ok(0),
             }
         }
     }
 
-    fn refill(&mut self) -> Result<usize, io::Error> {
-        let read_bytes = self.reader.read(&mut self.buffer_refill[..])?;
-        self.buffer.extend(self.buffer_refill[..read_bytes].iter());
+    #[test]
+    fn refill_would_block() {
+        let reader = Script(VecDeque::from(vec![
+            Ok(vec![0; 100]),
+            Err(io::ErrorKind::Interrupted.into()),
+            Err(io::ErrorKind::WouldBlock.into()),
+            Ok(Vec::new()),
+        ]));
+        let mut decoder = Decoder::new(reader);
+
+        assert!(matches!(decoder.next_frame(), Err(Error::WouldBlock)));
+        assert_eq!(decoder.core.buffered(), 100);
+        // Only the end of the stream is left to read.
+        assert_eq!(decoder.reader.0.len(), 1);
 
-        Ok(read_bytes)
+        assert!(matches!(decoder.next_frame(), Err(Error::Eof)));
+        assert!(matches!(decoder.next_frame(), Err(Error::Eof)));
+        assert!(decoder.reader.0.is_empty());
     }
 }
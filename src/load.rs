@@ -0,0 +1,89 @@
+use crate::{pcm_array, Decoder, Error, Sample, MAX_SAMPLES_PER_FRAME};
+use std::{fs::File, io, path::Path, time::Duration};
+
+// Most samples reserved up front from the length announced by the VBR tag,
+// about six minutes of 44.1 kHz stereo audio. Longer streams grow as needed.
+const MAX_RESERVED_SAMPLES: u64 = 1 << 25;
+
+/// The whole audio of a stream, as decoded by [`decode_all`].
+#[derive(Debug, Clone)]
+pub struct DecodedAudio {
+    /// The decoded samples, interleaved.
+    pub samples: Vec<Sample>,
+    /// Sample rate of the audio.
+    pub sample_rate: i32,
+    /// Number of channels of the audio.
+    pub channels: usize,
+}
+
+impl DecodedAudio {
+    /// The duration of the audio.
+    pub fn duration(&self) -> Duration {
+        let samples = (self.samples.len() / self.channels.max(1)) as u64;
+        let sample_rate = self.sample_rate.max(1) as u64;
+
+        Duration::new(
+            samples / sample_rate,
+            ((samples % sample_rate) * 1_000_000_000 / sample_rate) as u32,
+        )
+    }
+}
+
+/// Decodes a whole MP3 stream into memory, like `mp3dec_load` from
+/// minimp3_ex.
+///
+/// Gapless playback is enabled (see [`Decoder::set_gapless`]), so the encoder
+/// delay and padding are trimmed off the audio. Garbage between frames is
+/// skipped, but a change of sample rate or number of channels in the middle of
+/// the stream is reported as [`Error::FormatChanged`]. Returns [`Error::Eof`]
+/// if the stream has no frames at all.
+pub fn decode_all<R: io::Read>(reader: R) -> Result<DecodedAudio, Error> {
+    let mut decoder = Decoder::new(reader);
+    decoder.set_gapless(true);
+
+    // The frame count of the VBR tag is only a hint, which a damaged or
+    // crafted stream can set to anything, so the space reserved from it is
+    // capped and failing to reserve it isn't fatal.
+    let mut samples = Vec::new();
+    if let Some(total) = decoder
+        .read_stream_info()?
+        .and_then(|info| info.total_samples()?.checked_mul(info.channels as u64))
+    {
+        let _ = samples.try_reserve(total.min(MAX_RESERVED_SAMPLES) as usize);
+    }
+
+    let mut pcm = vec![Sample::default(); MAX_SAMPLES_PER_FRAME];
+    let mut format = None;
+    loop {
+        let info = match decoder.next_frame_into(pcm_array(&mut pcm)) {
+            Ok(info) => info,
+            Err(Error::Eof) => break,
+            Err(e) => return Err(e),
+        };
+
+        match format {
+            None => format = Some((info.sample_rate, info.channels)),
+            Some(format) if format != (info.sample_rate, info.channels) => {
+                return Err(Error::FormatChanged {
+                    sample_rate: info.sample_rate,
+                    channels: info.channels,
+                })
+            }
+            Some(_) => {}
+        }
+
+        samples.extend_from_slice(&pcm[..info.samples * info.channels]);
+    }
+
+    let (sample_rate, channels) = format.ok_or(Error::Eof)?;
+    Ok(DecodedAudio {
+        samples,
+        sample_rate,
+        channels,
+    })
+}
+
+/// Decodes the whole MP3 file at `path` into memory. See [`decode_all`].
+pub fn decode_file<P: AsRef<Path>>(path: P) -> Result<DecodedAudio, Error> {
+    decode_all(File::open(path)?)
+}
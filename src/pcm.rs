@@ -0,0 +1,163 @@
+use crate::{pcm_array, Decoder, Error, FrameInfo, Sample, MAX_SAMPLES_PER_FRAME};
+use std::{io, mem};
+#[cfg(any(feature = "async_tokio", feature = "async_futures"))]
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+const SAMPLE_SIZE: usize = mem::size_of::<Sample>();
+
+/// Adapts a [`Decoder`] into a reader of raw PCM bytes: interleaved, little
+/// endian [`Sample`]s (`i16`, or `f32` with the `float_output` feature).
+///
+/// Implements [`io::Read`], `tokio::io::AsyncRead` with the `async_tokio`
+/// feature and `futures_io::AsyncRead` with the `async_futures` feature, so
+/// that decoded audio can be piped into anything expecting a byte stream.
+/// Frames are decoded as needed, and samples that don't fit in the caller's
+/// buffer are kept for the next read.
+pub struct PcmReader<R> {
+    decoder: Decoder<R>,
+    pcm: Vec<Sample>,
+    info: Option<FrameInfo>,
+    // Bytes of the current frame already read.
+    pos: usize,
+}
+
+impl<R> PcmReader<R> {
+    /// Creates a new reader of the audio decoded by `decoder`.
+    pub fn new(decoder: Decoder<R>) -> Self {
+        Self {
+            decoder,
+            pcm: vec![Sample::default(); MAX_SAMPLES_PER_FRAME],
+            info: None,
+            pos: 0,
+        }
+    }
+
+    /// Return the information of the frame currently being read, which tells
+    /// the sample rate and the number of channels of the audio.
+    pub fn frame_info(&self) -> Option<&FrameInfo> {
+        self.info.as_ref()
+    }
+
+    /// Return a reference to the underlying decoder.
+    pub fn decoder(&self) -> &Decoder<R> {
+        &self.decoder
+    }
+
+    /// Return a mutable reference to the underlying decoder (decoding from it
+    /// skips audio).
+    pub fn decoder_mut(&mut self) -> &mut Decoder<R> {
+        &mut self.decoder
+    }
+
+    /// Destroy the reader and return the inner decoder. Any audio decoded but
+    /// not read yet is lost.
+    pub fn into_inner(self) -> Decoder<R> {
+        self.decoder
+    }
+
+    fn remaining(&self) -> usize {
+        self.info
+            .map_or(0, |info| info.samples * info.channels * SAMPLE_SIZE)
+            - self.pos
+    }
+
+    // Copies as many pending bytes as fit into `buf`.
+    fn copy_to(&mut self, buf: &mut [u8]) -> usize {
+        let len = self.remaining().min(buf.len());
+        for byte in &mut buf[..len] {
+            let sample = self.pcm[self.pos / SAMPLE_SIZE].to_le_bytes();
+            *byte = sample[self.pos % SAMPLE_SIZE];
+            self.pos += 1;
+        }
+
+        len
+    }
+
+    fn start_frame(&mut self, result: Result<FrameInfo, Error>) -> io::Result<bool> {
+        self.pos = 0;
+        self.info = None;
+        match result {
+            Ok(info) => {
+                self.info = Some(info);
+                Ok(true)
+            }
+            Err(Error::Eof) => Ok(false),
+            Err(Error::WouldBlock) => Err(io::ErrorKind::WouldBlock.into()),
+            Err(Error::Io(e)) => Err(e),
+            Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+    }
+}
+
+impl<R: io::Read> io::Read for PcmReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.remaining() == 0 {
+            let result = self.decoder.next_frame_into(pcm_array(&mut self.pcm));
+            if !self.start_frame(result)? {
+                return Ok(0);
+            }
+        }
+
+        Ok(self.copy_to(buf))
+    }
+}
+
+#[cfg(feature = "async_tokio")]
+impl<R: tokio::io::AsyncRead + Unpin> tokio::io::AsyncRead for PcmReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        if this.remaining() == 0 {
+            let result = ready!(this
+                .decoder
+                .poll_next_frame_into::<crate::TokioIo>(cx, pcm_array(&mut this.pcm)));
+            if !this.start_frame(result)? {
+                return Poll::Ready(Ok(()));
+            }
+        }
+
+        let len = this.copy_to(buf.initialize_unfilled());
+        buf.advance(len);
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "async_futures")]
+impl<R: futures_io::AsyncRead + Unpin> futures_io::AsyncRead for PcmReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        if this.remaining() == 0 {
+            let result = ready!(this
+                .decoder
+                .poll_next_frame_into::<crate::FuturesIo>(cx, pcm_array(&mut this.pcm)));
+            if !this.start_frame(result)? {
+                return Poll::Ready(Ok(0));
+            }
+        }
+
+        Poll::Ready(Ok(this.copy_to(buf)))
+    }
+}
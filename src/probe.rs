@@ -0,0 +1,192 @@
+use crate::{
+    header::{FrameHeader, HEADER_LEN},
+    id3v2, info, BitrateMode, Error, StreamInfo,
+};
+use std::{
+    convert::{TryFrom, TryInto},
+    io::{self, Read},
+};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Gathers information about a whole MP3 stream by walking its frame headers,
+/// without decoding any audio.
+///
+/// Every frame of the stream is visited, so the frame count, duration and
+/// average bitrate are exact even for streams without a VBR tag (the byte
+/// count is left unknown past 4 GiB, which [`StreamInfo::bytes`] can't hold).
+/// If the stream has a VBR tag, its contents (such as the LAME extension) are
+/// reported as well. Free format frames, whose size isn't stored in their
+/// header, are skipped, as are frames of layers the decoder doesn't support
+/// (see [`Layer::is_supported`](crate::Layer::is_supported)).
+pub fn probe<R: io::Read>(reader: R) -> Result<StreamInfo, Error> {
+    let mut scanner = Scanner {
+        reader,
+        buffer: Vec::with_capacity(CHUNK_SIZE * 2),
+        pos: 0,
+        eof: false,
+    };
+
+    // A tag longer than the stream has a corrupt header, or is garbage which
+    // looks like one, so it is only skipped once buffered whole, for frames
+    // to be looked for in what it would span otherwise.
+    if scanner.fill(id3v2::HEADER_LEN)? {
+        if let Some(len) = id3v2::tag_len(scanner.data()) {
+            if scanner.fill(len)? {
+                scanner.skip(len)?;
+            }
+        }
+    }
+
+    let mut info: Option<StreamInfo> = None;
+    let mut first_bitrate = None;
+    let mut bitrate_mode = BitrateMode::Constant;
+    let mut frames = 0u32;
+    let mut bytes = 0u64;
+    // Whether the last bytes scanned were a frame, or garbage.
+    let mut in_sync = false;
+
+    while scanner.fill(HEADER_LEN)? {
+        let header = match scanner.header(0) {
+            Some(header) => header,
+            None => {
+                scanner.skip(1)?;
+                in_sync = false;
+                continue;
+            }
+        };
+        let len = match header.frame_len() {
+            Some(len) if len > HEADER_LEN => len,
+            _ => {
+                scanner.skip(1)?;
+                in_sync = false;
+                continue;
+            }
+        };
+
+        // Guard against garbage that happens to look like a header when
+        // resynchronising, by also checking for the header of the next frame.
+        if !in_sync && scanner.fill(len + HEADER_LEN)? {
+            match scanner.header(len) {
+                Some(next)
+                    if next.version == header.version
+                        && next.layer == header.layer
+                        && next.sample_rate == header.sample_rate => {}
+                _ => {
+                    scanner.skip(1)?;
+                    continue;
+                }
+            }
+        }
+        in_sync = true;
+
+        if !header.layer.is_supported() {
+            scanner.skip(len)?;
+            continue;
+        }
+
+        if info.is_none() {
+            scanner.fill(len)?;
+            let frame = &scanner.data()[..len.min(scanner.data().len())];
+            if let Some(tag) = info::parse(frame) {
+                info = Some(tag);
+                scanner.skip(len)?;
+                continue;
+            }
+
+            info = Some(StreamInfo {
+                tag: None,
+                sample_rate: header.sample_rate as i32,
+                channels: header.channels(),
+                layer: header.layer as usize + 1,
+                samples_per_frame: header.samples_per_frame(),
+                frames: None,
+                bytes: None,
+                toc: None,
+                quality: None,
+                lame: None,
+                bitrate_mode: None,
+                bitrate: None,
+            });
+        }
+
+        match first_bitrate {
+            None => first_bitrate = header.bitrate,
+            Some(bitrate) if header.bitrate != Some(bitrate) => {
+                bitrate_mode = BitrateMode::Variable
+            }
+            Some(_) => {}
+        }
+        frames += 1;
+        bytes += len as u64;
+        scanner.skip(len)?;
+    }
+
+    let mut info = info.ok_or(Error::Eof)?;
+    info.frames = Some(frames);
+    info.bytes = u32::try_from(bytes).ok();
+    info.bitrate_mode = Some(bitrate_mode);
+    info.bitrate = info.average_bitrate(bytes);
+
+    Ok(info)
+}
+
+// Buffers just enough of a reader to look at one frame at a time.
+struct Scanner<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    pos: usize,
+    eof: bool,
+}
+
+impl<R: io::Read> Scanner<R> {
+    fn data(&self) -> &[u8] {
+        &self.buffer[self.pos..]
+    }
+
+    fn header(&self, offset: usize) -> Option<FrameHeader> {
+        let bytes = self.data().get(offset..offset + HEADER_LEN)?;
+        FrameHeader::parse(bytes.try_into().ok()?)
+    }
+
+    // Buffers at least `len` bytes. Returns false if the reader ends first.
+    fn fill(&mut self, len: usize) -> io::Result<bool> {
+        while self.buffer.len() - self.pos < len && !self.eof {
+            if self.pos > 0 {
+                self.buffer.drain(..self.pos);
+                self.pos = 0;
+            }
+
+            let filled = self.buffer.len();
+            self.buffer.resize(filled + CHUNK_SIZE, 0);
+            let result = self.reader.read(&mut self.buffer[filled..]);
+            self.buffer
+                .truncate(filled + *result.as_ref().unwrap_or(&0));
+            match result {
+                Ok(read) => self.eof = read == 0,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(self.buffer.len() - self.pos >= len)
+    }
+
+    fn skip(&mut self, len: usize) -> io::Result<()> {
+        let buffered = self.buffer.len() - self.pos;
+        if len <= buffered {
+            self.pos += len;
+            return Ok(());
+        }
+
+        self.buffer.clear();
+        self.pos = 0;
+        let remaining = (len - buffered) as u64;
+        let skipped = io::copy(&mut (&mut self.reader).take(remaining), &mut io::sink())?;
+        if skipped < remaining {
+            self.eof = true;
+        }
+
+        Ok(())
+    }
+}
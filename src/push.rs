@@ -0,0 +1,133 @@
+use crate::{
+    pcm_array, DecodeStats, DecoderCore, Frame, FrameInfo, Id3v2Tag, Sample, StreamInfo,
+    MAX_SAMPLES_PER_FRAME,
+};
+use alloc::{vec, vec::Vec};
+use core::mem;
+
+/// A MP3 decoder which is fed the stream as it arrives, in chunks of any size,
+/// for sources that aren't readers: network messages, packet payloads or
+/// callbacks.
+///
+/// Frames are only decoded once a few of them are buffered (about 18 kB, see
+/// [`DecoderBuilder::refill_threshold`]), so that minimp3 can tell frames from
+/// garbage, until [`finish`] is called to flush the rest of the stream.
+///
+/// ```no_run
+/// # use minimp3::PushDecoder;
+/// # let packets: Vec<Vec<u8>> = Vec::new();
+/// let mut decoder = PushDecoder::new();
+///
+/// for packet in packets {
+///     decoder.feed(&packet);
+///     while let Some(frame) = decoder.poll_frame() {
+///         println!("Decoded {} samples", frame.data.len() / frame.channels);
+///     }
+/// }
+///
+/// decoder.finish();
+/// while let Some(frame) = decoder.poll_frame() {
+///     println!("Decoded {} samples", frame.data.len() / frame.channels);
+/// }
+/// ```
+///
+/// [`DecoderBuilder::refill_threshold`]: crate::DecoderBuilder::refill_threshold
+/// [`finish`]: PushDecoder::finish
+pub struct PushDecoder {
+    core: DecoderCore,
+    // Decoded audio of the last frame, for the methods not given a buffer.
+    pcm: Vec<Sample>,
+}
+
+impl Default for PushDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PushDecoder {
+    /// Creates a new decoder, with no data fed yet.
+    pub fn new() -> Self {
+        Self::with_core(DecoderCore::new())
+    }
+
+    pub(crate) fn with_core(core: DecoderCore) -> Self {
+        Self {
+            core,
+            pcm: vec![Sample::default(); MAX_SAMPLES_PER_FRAME],
+        }
+    }
+
+    /// Appends `data` to the stream being decoded.
+    pub fn feed(&mut self, data: &[u8]) {
+        self.core.push(data);
+    }
+
+    /// Marks the end of the stream, so that the frames still buffered are
+    /// decoded by the next calls to [`poll_frame`](PushDecoder::poll_frame).
+    pub fn finish(&mut self) {
+        self.core.finish();
+    }
+
+    /// Decodes the next frame, if enough data has been fed. Returns `None` when
+    /// more data is needed, or once the whole stream has been decoded after
+    /// [`finish`](PushDecoder::finish).
+    pub fn poll_frame(&mut self) -> Option<Frame> {
+        let mut pcm = mem::take(&mut self.pcm);
+        let info = self.poll_frame_into(pcm_array(&mut pcm));
+        self.pcm = pcm;
+
+        let info = info?;
+        Some(Frame::new(
+            self.pcm[..info.samples * info.channels].to_vec(),
+            info,
+        ))
+    }
+
+    /// Decodes the next frame into `pcm`, without allocating. Returns the
+    /// [`FrameInfo`] of the frame, whose decoded audio is held in the first
+    /// `samples * channels` samples of `pcm`. See
+    /// [`poll_frame`](PushDecoder::poll_frame).
+    pub fn poll_frame_into(
+        &mut self,
+        pcm: &mut [Sample; MAX_SAMPLES_PER_FRAME],
+    ) -> Option<FrameInfo> {
+        // Without IO, the core only ever asks for more data or reports the
+        // end of the stream.
+        self.core.decode_into(pcm).ok()
+    }
+
+    /// Return the number of bytes fed and not decoded yet.
+    pub fn buffered(&self) -> usize {
+        self.core.buffered()
+    }
+
+    /// Return the ID3v2 tag found at the start of the stream, if any.
+    pub fn id3v2(&self) -> Option<&Id3v2Tag> {
+        self.core.id3v2()
+    }
+
+    /// Return the information read from the VBR tag (Xing, Info or VBRI) of
+    /// the stream, if it has one.
+    pub fn stream_info(&self) -> Option<&StreamInfo> {
+        self.core.stream_info()
+    }
+
+    /// Return statistics about the data decoded so far. See
+    /// [`DecoderCore::stats`].
+    pub fn stats(&self) -> &DecodeStats {
+        self.core.stats()
+    }
+
+    /// Enables or disables gapless playback. See
+    /// [`DecoderCore::set_gapless`].
+    pub fn set_gapless(&mut self, enabled: bool) {
+        self.core.set_gapless(enabled);
+    }
+
+    /// Enables or disables the parsing of the ID3v2 tag. See
+    /// [`DecoderCore::set_parse_id3v2`].
+    pub fn set_parse_id3v2(&mut self, enabled: bool) {
+        self.core.set_parse_id3v2(enabled);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{Decoder, DecoderCore, Error};
+use std::{
+    io::{self, SeekFrom},
+    mem,
+    time::Duration,
+};
+
+// Largest amount of main data a layer III frame can borrow from the frames
+// preceding it (the size of the bit reservoir).
+const MAX_RESERVOIR_BYTES: u64 = 511;
+
+// Minimum number of frames to decode and discard before the frame being
+// seeked to, so that the synthesis filterbank overlap is warmed up.
+const PREDECODE_FRAMES: usize = 2;
+
+#[derive(Debug, Clone, Copy)]
+struct IndexEntry {
+    // Byte offset of the frame header in the stream.
+    offset: u64,
+    // Index of the first sample (per channel) of the frame.
+    sample: u64,
+}
+
+/// Position of every frame in a stream, built on the first seek.
+pub(crate) struct SeekIndex {
+    frames: Vec<IndexEntry>,
+    sample_rate: u32,
+    samples: u64,
+    // Byte offset of the end of the stream.
+    end: u64,
+}
+
+impl<R: io::Read + io::Seek> Decoder<R> {
+    /// Seeks to the given sample (counted per channel, from the start of the
+    /// stream), so that the next [`Frame`](crate::Frame) returned starts
+    /// exactly at that sample.
+    ///
+    /// The first seek scans the whole reader to build an index of frame
+    /// positions, so it is proportionally slower than the following ones.
+    /// Seeking past the end of the stream leaves the decoder at its end.
+    pub fn seek_to_sample(&mut self, sample: u64) -> Result<(), Error> {
+        if self.index.is_none() {
+            self.index = Some(self.build_index()?);
+        }
+        // Positions are relative to the trimmed audio in gapless mode.
+        self.core.start_gapless();
+        let sample = sample
+            + self
+                .core
+                .gapless
+                .as_ref()
+                .map_or(0, |gapless| gapless.delay as u64);
+
+        let index = self.index.as_ref().unwrap();
+        if sample >= index.samples {
+            let end = index.end;
+            return self.reset(end);
+        }
+
+        // Frame containing the requested sample.
+        let target = match index.frames.binary_search_by_key(&sample, |f| f.sample) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        };
+
+        // The frames before the target one have to be decoded for their
+        // contribution to the bit reservoir and the filterbank state.
+        let mut start = target;
+        while start > 0
+            && (target - start < PREDECODE_FRAMES
+                || index.frames[target - 1].offset - index.frames[start].offset
+                    < MAX_RESERVOIR_BYTES)
+        {
+            start -= 1;
+        }
+
+        let start_offset = index.frames[start].offset;
+        let skip = (sample - index.frames[target].sample) as usize;
+
+        self.reset(start_offset)?;
+        for _ in start..target {
+            self.skip_frame()?;
+        }
+        self.core.skip = skip;
+
+        Ok(())
+    }
+
+    /// Skips the ID3v2 tag at the start of the stream by seeking past it,
+    /// rather than reading it. The tag isn't parsed then, see
+    /// [`set_parse_id3v2`](Decoder::set_parse_id3v2). Must be called before
+    /// the first frame is decoded.
+    pub fn seek_past_id3v2(&mut self) {
+        self.core.set_parse_id3v2(false);
+        self.seek_input = Some(|reader, len| {
+            reader.seek(SeekFrom::Current(len as i64))?;
+            Ok(())
+        });
+    }
+
+    /// Seeks to the given point in time. See
+    /// [`seek_to_sample`](Decoder::seek_to_sample).
+    pub fn seek_to_duration(&mut self, duration: Duration) -> Result<(), Error> {
+        if self.index.is_none() {
+            self.index = Some(self.build_index()?);
+        }
+        let sample_rate = u64::from(self.index.as_ref().unwrap().sample_rate);
+        let sample = duration.as_secs() * sample_rate
+            + u64::from(duration.subsec_nanos()) * sample_rate / 1_000_000_000;
+
+        self.seek_to_sample(sample)
+    }
+
+    fn build_index(&mut self) -> Result<SeekIndex, Error> {
+        if self.core.end.is_none() {
+            self.read_trailing_tags()?;
+        }
+
+        // Go through the ID3v2 and VBR tags again, which don't hold audio.
+        self.reset(0)?;
+        self.core.id3v2_checked = false;
+        self.core.tag_checked = false;
+
+        // Scanning isn't decoding: it mustn't show in the stats, nor stop at
+        // corrupt data in strict mode.
+        let stats = mem::take(&mut self.core.stats);
+        let strict = mem::replace(&mut self.core.strict, false);
+        let result = self.scan_frames();
+        self.core.stats = stats;
+        self.core.strict = strict;
+
+        let (frames, sample_rate, samples) = result?;
+        Ok(SeekIndex {
+            frames,
+            sample_rate,
+            samples,
+            end: self.core.offset + self.core.buffered() as u64,
+        })
+    }
+
+    // Parses the header of every frame left in the stream, returning their
+    // positions, the sample rate and the total number of samples.
+    fn scan_frames(&mut self) -> Result<(Vec<IndexEntry>, u32, u64), Error> {
+        let mut frames = Vec::new();
+        let mut sample_rate = 0;
+        let mut samples = 0;
+
+        loop {
+            let (offset, frame_samples, hz) = match self.drive(DecoderCore::scan_frame) {
+                Ok(frame) => frame,
+                Err(Error::Eof) => break,
+                Err(e) => return Err(e),
+            };
+
+            frames.push(IndexEntry {
+                offset,
+                sample: samples,
+            });
+            samples += frame_samples;
+            if sample_rate == 0 {
+                sample_rate = hz;
+            }
+        }
+
+        Ok((frames, sample_rate, samples))
+    }
+
+    // Decodes and discards exactly one frame, regardless of whether it produced
+    // any audio.
+    fn skip_frame(&mut self) -> Result<(), Error> {
+        match self.drive(DecoderCore::skip_frame) {
+            Ok(()) | Err(Error::Eof) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn reset(&mut self, offset: u64) -> Result<(), Error> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.core.reset(offset);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Decoder, Error};
+    use std::io::{self, Cursor, Read, Seek, SeekFrom};
+
+    // A reader counting the bytes read from it.
+    struct Counting {
+        inner: Cursor<Vec<u8>>,
+        read: usize,
+    }
+
+    impl Read for Counting {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.inner.read(buf)?;
+            self.read += len;
+            Ok(len)
+        }
+    }
+
+    impl Seek for Counting {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn seek_past_id3v2() {
+        // An ID3v2.3 tag of 1 MiB, followed by 1000 bytes.
+        let mut data = b"ID3\x03\0\0\x00\x40\x00\x00".to_vec();
+        data.resize((1 << 20) + 10 + 1000, 0);
+        let reader = Counting {
+            inner: Cursor::new(data),
+            read: 0,
+        };
+
+        let mut decoder = Decoder::new(reader);
+        decoder.seek_past_id3v2();
+        assert!(matches!(decoder.next_frame(), Err(Error::Eof)));
+        assert!(decoder.id3v2().is_none());
+        assert!(decoder.reader().read < 64 * 1024);
+        assert_eq!(decoder.reader().inner.position(), (1 << 20) + 10 + 1000);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{
+    ffi, header, id3v2, info, pcm_array, trailers, ApeTag, Error, Frame, FrameHeader, FrameInfo,
+    Id3v1Tag, Id3v2Tag, Sample, StreamInfo, MAX_SAMPLES_PER_FRAME,
+};
+use alloc::{boxed::Box, vec};
+use core::{convert::TryInto, mem, ptr};
+
+/// A MP3 decoder which decodes frames straight from a byte slice, such as a
+/// file loaded or mapped in memory, without copying it.
+///
+/// The ID3v2 and VBR tags at the start of the slice, and the ID3v1 and APEv2
+/// tags at its end, are parsed when the decoder is created.
+pub struct SliceDecoder<'a> {
+    data: &'a [u8],
+    offset: usize,
+    decoder: Box<ffi::mp3dec_t>,
+    id3v2: Option<Id3v2Tag>,
+    id3v1: Option<Id3v1Tag>,
+    ape: Option<ApeTag>,
+    info: Option<StreamInfo>,
+    // Header of the last frame decoded, to tell where the next one ends.
+    last_header: Option<FrameHeader>,
+}
+
+impl<'a> SliceDecoder<'a> {
+    /// Creates a new decoder reading from `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        let mut minidec = unsafe { Box::new(mem::zeroed()) };
+        unsafe { ffi::mp3dec_init(&mut *minidec) }
+
+        let trailers = trailers::parse(data);
+        let data = &data[..data.len() - trailers.len];
+
+        let mut decoder = Self {
+            data,
+            offset: 0,
+            decoder: minidec,
+            id3v2: None,
+            id3v1: trailers.id3v1,
+            ape: trailers.ape,
+            info: None,
+            last_header: None,
+        };
+
+        // A tag longer than the data has a corrupt header, or is garbage
+        // which looks like one, so frames are looked for in what it would span.
+        if let Some(len) = id3v2::tag_len(data).filter(|&len| len <= data.len()) {
+            decoder.id3v2 = id3v2::parse(&data[..len]);
+            decoder.offset = len;
+        }
+        decoder.check_tag();
+
+        decoder
+    }
+
+    /// Return the slice being decoded, without the ID3v1 and APEv2 tags at
+    /// its end.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Return the offset in the slice of the next byte to be decoded.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the ID3v2 tag found at the start of the slice, if any.
+    pub fn id3v2(&self) -> Option<&Id3v2Tag> {
+        self.id3v2.as_ref()
+    }
+
+    /// Return the ID3v1 tag found at the end of the slice, if any.
+    pub fn id3v1(&self) -> Option<&Id3v1Tag> {
+        self.id3v1.as_ref()
+    }
+
+    /// Return the APEv2 tag found at the end of the slice, if any.
+    pub fn ape(&self) -> Option<&ApeTag> {
+        self.ape.as_ref()
+    }
+
+    /// Return the information read from the VBR tag (Xing, Info or VBRI) of
+    /// the stream, if it has one.
+    pub fn stream_info(&self) -> Option<&StreamInfo> {
+        self.info.as_ref()
+    }
+
+    /// Decodes the next frame. Returns a [`Frame`] if one was found, or,
+    /// otherwise, an `Err` explaining why not. Returns [`Error::Eof`] once
+    /// the end of the slice is reached.
+    pub fn next_frame(&mut self) -> Result<Frame, Error> {
+        let mut pcm = vec![Sample::default(); MAX_SAMPLES_PER_FRAME];
+        let info = self.next_frame_into(pcm_array(&mut pcm))?;
+        pcm.truncate(info.samples * info.channels);
+
+        Ok(Frame::new(pcm, info))
+    }
+
+    /// Decodes the next frame into `pcm`, without allocating. Returns the
+    /// [`FrameInfo`] of the frame, whose decoded audio is held in the first
+    /// `samples * channels` samples of `pcm`, or, otherwise, an `Err`
+    /// explaining why not.
+    pub fn next_frame_into(
+        &mut self,
+        pcm: &mut [Sample; MAX_SAMPLES_PER_FRAME],
+    ) -> Result<FrameInfo, Error> {
+        loop {
+            match self.decode_frame(pcm) {
+                Ok(info) => return Ok(info),
+                Err(Error::SkippedData) => {}
+                Err(Error::InsufficientData) => return Err(Error::Eof),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn decode_frame(
+        &mut self,
+        pcm: &mut [Sample; MAX_SAMPLES_PER_FRAME],
+    ) -> Result<FrameInfo, Error> {
+        let data = &self.data[self.offset..];
+        let len = self.final_frame_len(data).unwrap_or(data.len());
+        let mut frame_info: ffi::mp3dec_frame_info_t = unsafe { mem::zeroed() };
+        let samples: usize = unsafe {
+            ffi::mp3dec_decode_frame(
+                &mut *self.decoder,
+                data.as_ptr(),
+                len as _,
+                pcm.as_mut_ptr(),
+                &mut frame_info,
+            ) as _
+        };
+
+        self.offset += frame_info.frame_bytes as usize;
+
+        if samples == 0 {
+            if frame_info.frame_bytes > 0 {
+                Err(Error::SkippedData)
+            } else {
+                Err(Error::InsufficientData)
+            }
+        } else {
+            let info = FrameInfo::new(samples, &frame_info, data);
+            self.last_header = Some(info.header);
+            Ok(info)
+        }
+    }
+
+    // Length of the frame starting `data` if it is the last one of the slice.
+    // minimp3 only accepts a frame followed by the header of another one, or
+    // one which fills the data it is given exactly, so the last frame has to
+    // be passed on its own, as `DecoderCore::peek_frame` does once the input
+    // is finished.
+    fn final_frame_len(&self, data: &[u8]) -> Option<usize> {
+        let last = self.last_header.as_ref()?;
+        let len = last.whole_frame_len(data)?;
+
+        match last.whole_frame_len(&data[len..]) {
+            Some(_) => None,
+            None => Some(len),
+        }
+    }
+
+    // Skips the first frame if it holds a VBR tag, so that it isn't decoded as
+    // a frame of silence.
+    fn check_tag(&mut self) {
+        let data = &self.data[self.offset..];
+        let mut frame_info: ffi::mp3dec_frame_info_t = unsafe { mem::zeroed() };
+        let samples = unsafe {
+            ffi::mp3dec_decode_frame(
+                &mut *self.decoder,
+                data.as_ptr(),
+                data.len() as _,
+                ptr::null_mut(),
+                &mut frame_info,
+            )
+        };
+
+        if samples > 0 {
+            let frame = &data[frame_info.frame_offset as usize..frame_info.frame_bytes as usize];
+            self.last_header = frame
+                .get(..header::HEADER_LEN)
+                .and_then(|bytes| bytes.try_into().ok())
+                .and_then(FrameHeader::parse);
+            self.info = info::parse(frame);
+            if self.info.is_some() {
+                self.offset += frame_info.frame_bytes as usize;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn stream(tag_len: usize, len: usize) -> Vec<u8> {
+        let size = tag_len - id3v2::HEADER_LEN;
+        let mut data = b"ID3\x03\0\0".to_vec();
+        data.extend((0..4).rev().map(|i| (size >> (i * 7)) as u8 & 0x7f));
+        data.resize(len, 0);
+        data
+    }
+
+    #[test]
+    fn id3v2_tag_is_skipped() {
+        let data = stream(100, 300);
+        let decoder = SliceDecoder::new(&data);
+        assert_eq!(decoder.offset(), 100);
+        assert!(decoder.id3v2().is_some());
+    }
+
+    #[test]
+    fn id3v2_tag_longer_than_data_is_garbage() {
+        let data = stream(1000, 300);
+        let decoder = SliceDecoder::new(&data);
+        assert_eq!(decoder.offset(), 0);
+        assert!(decoder.id3v2().is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{ape, id3v1, ApeTag, Id3v1Tag};
+#[cfg(feature = "std")]
+use crate::{Decoder, Error};
+#[cfg(feature = "std")]
+use std::io::{self, SeekFrom};
+
+#[cfg(feature = "std")]
+impl<R: io::Read + io::Seek> Decoder<R> {
+    /// Looks for ID3v1 and APEv2 tags at the end of the reader, and excludes
+    /// them from the audio being decoded so that they aren't mistaken for
+    /// frames. Their contents are available through
+    /// [`id3v1`](Decoder::id3v1) and [`ape`](Decoder::ape) afterwards.
+    ///
+    /// The position of the reader is restored before returning. This is done
+    /// automatically when seeking for the first time.
+    pub fn read_trailing_tags(&mut self) -> Result<(), Error> {
+        let position = self.reader.stream_position()?;
+        let mut end = self.reader.seek(SeekFrom::End(0))?;
+
+        if end >= id3v1::TAG_LEN as u64 {
+            let mut data = [0; id3v1::TAG_LEN];
+            self.reader
+                .seek(SeekFrom::Start(end - id3v1::TAG_LEN as u64))?;
+            self.reader.read_exact(&mut data)?;
+            self.id3v1 = id3v1::parse(&data);
+            if self.id3v1.is_some() {
+                end -= id3v1::TAG_LEN as u64;
+            }
+        }
+
+        // APEv2 tags go either last, or right before an ID3v1 tag.
+        if end >= ape::FOOTER_LEN as u64 {
+            let mut data = [0; ape::FOOTER_LEN];
+            self.reader
+                .seek(SeekFrom::Start(end - ape::FOOTER_LEN as u64))?;
+            self.reader.read_exact(&mut data)?;
+
+            match ape::Footer::parse(&data) {
+                Some(footer) if footer.tag_len() <= end => {
+                    let mut items = vec![0; footer.items_len()];
+                    self.reader.seek(SeekFrom::Start(
+                        end - (ape::FOOTER_LEN + footer.items_len()) as u64,
+                    ))?;
+                    self.reader.read_exact(&mut items)?;
+                    self.ape = Some(footer.parse_items(&items));
+                    end -= footer.tag_len();
+                }
+                _ => {}
+            }
+        }
+
+        self.reader.seek(SeekFrom::Start(position))?;
+        self.core.set_end(end);
+
+        Ok(())
+    }
+}
+
+// The trailing tags found at the end of data held in memory.
+pub(crate) struct Trailers {
+    // Number of bytes taken by the tags at the end of the data.
+    pub(crate) len: usize,
+    pub(crate) id3v1: Option<Id3v1Tag>,
+    pub(crate) ape: Option<ApeTag>,
+}
+
+// Parses the ID3v1 and APEv2 tags at the end of `data`, the way
+// `read_trailing_tags` does from a reader.
+pub(crate) fn parse(data: &[u8]) -> Trailers {
+    let mut end = data.len();
+
+    let id3v1 = data
+        .len()
+        .checked_sub(id3v1::TAG_LEN)
+        .and_then(|start| id3v1::parse(&data[start..]));
+    if id3v1.is_some() {
+        end -= id3v1::TAG_LEN;
+    }
+
+    let mut ape = None;
+    if end >= ape::FOOTER_LEN {
+        match ape::Footer::parse(&data[end - ape::FOOTER_LEN..end]) {
+            Some(footer) if footer.tag_len() <= end as u64 => {
+                let items_end = end - ape::FOOTER_LEN;
+                ape = Some(footer.parse_items(&data[items_end - footer.items_len()..items_end]));
+                end -= footer.tag_len() as usize;
+            }
+            _ => {}
+        }
+    }
+
+    Trailers {
+        len: data.len() - end,
+        id3v1,
+        ape,
+    }
+}
+
+// Number of bytes at the end of `data` taken by trailing tags, for streams
+// whose end is only found by reading it.
+pub(crate) fn tags_len(data: &[u8]) -> usize {
+    parse(data).len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn ape_footer(size: u32) -> [u8; ape::FOOTER_LEN] {
+        let mut footer = [0; ape::FOOTER_LEN];
+        footer[..8].copy_from_slice(b"APETAGEX");
+        footer[8..12].copy_from_slice(&2000u32.to_le_bytes());
+        footer[12..16].copy_from_slice(&size.to_le_bytes());
+        footer
+    }
+
+    #[test]
+    fn ape_tag() {
+        let mut data = vec![0; 100];
+        data.extend_from_slice(&ape_footer(ape::FOOTER_LEN as u32 + 20));
+
+        let trailers = parse(&data);
+        assert_eq!(trailers.len, 52);
+        assert!(trailers.ape.is_some());
+        assert!(trailers.id3v1.is_none());
+    }
+
+    #[test]
+    fn ape_tag_longer_than_data() {
+        for &size in &[200, u32::MAX] {
+            let mut data = vec![0; 100];
+            data.extend_from_slice(&ape_footer(size));
+
+            let trailers = parse(&data);
+            assert_eq!(trailers.len, 0);
+            assert!(trailers.ape.is_none());
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Helpers shared by the tests using the test vectors of minimp3.
+
+use std::{fs, path::Path};
+
+const VECTOR: &str = "minimp3-sys/minimp3/vectors/M2L3_bitrate_24_all.bit";
+
+// The vectors come with the minimp3 submodule, but aren't packaged with the
+// crate, so the tests using them are ignored by default. Run them with
+// `cargo test -- --ignored` from a checkout with the submodule.
+pub fn vector() -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(VECTOR);
+    fs::read(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
@@ -0,0 +1,110 @@
+//! Checks that the end of truncated streams is drained, decoding every whole
+//! frame left in them, using the test vectors of minimp3.
+
+mod common;
+
+use common::vector;
+use minimp3::{DecodeStats, Decoder, Error, Frame, FrameHeader};
+use std::{convert::TryInto, io::Cursor};
+
+const JUNK: [u8; 100] = [0; 100];
+
+// Offsets of the frames of a stream made of frames only.
+fn frame_offsets(data: &[u8]) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut offset = 0;
+
+    while let Some(bytes) = data.get(offset..offset + 4) {
+        let len = match FrameHeader::parse(bytes.try_into().unwrap()) {
+            Some(header) => header.frame_len().expect("free format vector"),
+            None => break,
+        };
+        if offset + len > data.len() {
+            break;
+        }
+
+        offsets.push(offset);
+        offset += len;
+    }
+
+    offsets
+}
+
+fn decode(data: Vec<u8>) -> (Vec<Frame>, DecodeStats) {
+    let mut decoder = Decoder::new(Cursor::new(data));
+    let mut frames = Vec::new();
+
+    loop {
+        match decoder.next_frame() {
+            Ok(frame) => frames.push(frame),
+            Err(Error::Eof) => break,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    (frames, decoder.stats().clone())
+}
+
+// Checks that decoding `data` gives the frames of the whole vector, but for
+// the last `lost` ones.
+fn assert_truncated(data: Vec<u8>, full: &[Frame], lost: usize) -> DecodeStats {
+    let (frames, stats) = decode(data);
+
+    assert_eq!(frames.len(), full.len() - lost);
+    for (frame, expected) in frames.iter().zip(full) {
+        assert_eq!(frame.data, expected.data);
+    }
+
+    stats
+}
+
+#[test]
+#[ignore = "needs minimp3 vectors"]
+fn truncated_streams() {
+    let data = vector();
+    let offsets = frame_offsets(&data);
+    let (full, _) = decode(data.clone());
+
+    for &frame in &[offsets.len() / 2, offsets.len() - 1] {
+        let start = offsets[frame];
+        let len = offsets.get(frame + 1).copied().unwrap_or(data.len()) - start;
+        let lost = offsets.len() - frame;
+
+        // Cut right after a frame, within the next header or the next frame.
+        for &cut in &[0, 2, len / 2] {
+            assert_truncated(data[..start + cut].to_vec(), &full, lost);
+        }
+
+        // Followed by junk.
+        let mut junk = data[..start].to_vec();
+        junk.extend_from_slice(&JUNK);
+        let stats = assert_truncated(junk, &full, lost);
+        assert_eq!(stats.bytes_skipped, JUNK.len() as u64);
+    }
+}
+
+#[test]
+#[ignore = "needs minimp3 vectors"]
+fn frame_behind_junk() {
+    let data = vector();
+    let offsets = frame_offsets(&data);
+    let (full, full_stats) = decode(data.clone());
+
+    // The last frame comes after junk, so it is found but can't be decoded
+    // like in the whole stream, having lost its bit reservoir.
+    let start = offsets[offsets.len() - 1];
+    let mut stream = data[..start].to_vec();
+    stream.extend_from_slice(&JUNK);
+    stream.extend_from_slice(&data[start..]);
+
+    let (frames, stats) = decode(stream);
+    for (frame, expected) in frames.iter().zip(&full[..full.len() - 1]) {
+        assert_eq!(frame.data, expected.data);
+    }
+    assert_eq!(
+        stats.frames_decoded + stats.frames_dropped,
+        full_stats.frames_decoded + full_stats.frames_dropped
+    );
+    let junk = start as u64..(start + JUNK.len()) as u64;
+    assert_eq!(stats.gaps, vec![junk]);
+}
@@ -0,0 +1,74 @@
+//! Checks that seeking to a sample gives the same audio as decoding the whole
+//! stream from its start, using the test vectors of minimp3.
+
+mod common;
+
+use common::vector;
+use minimp3::{Decoder, Error, Sample};
+use std::io::Cursor;
+
+// Encoder delay and padding trimmed in gapless mode, as the vector has no
+// LAME tag of its own.
+const GAPLESS: (u16, u16) = (576, 1152);
+
+fn decoder(data: &[u8], gapless: bool) -> Decoder<Cursor<Vec<u8>>> {
+    let mut decoder = Decoder::new(Cursor::new(data.to_vec()));
+    if gapless {
+        decoder.set_gapless_override(GAPLESS.0, GAPLESS.1);
+    }
+    decoder
+}
+
+// Decodes the rest of the stream, returning its interleaved samples and the
+// number of channels.
+fn decode_rest(decoder: &mut Decoder<Cursor<Vec<u8>>>) -> (Vec<Sample>, usize) {
+    let mut samples = Vec::new();
+    let mut channels = 0;
+
+    loop {
+        match decoder.next_frame() {
+            Ok(frame) => {
+                channels = frame.channels;
+                samples.extend_from_slice(&frame.data);
+            }
+            Err(Error::Eof) => break,
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
+    (samples, channels)
+}
+
+fn assert_seeks(gapless: bool) {
+    let data = vector();
+    let (full, channels) = decode_rest(&mut decoder(&data, gapless));
+    let len = (full.len() / channels) as u64;
+
+    // The start, within the first frames, across frame boundaries, the middle
+    // and the very end of the stream.
+    for &sample in &[0, 1, 575, 1152, 1153, 5000, len / 2, len - 1, len] {
+        let mut decoder = decoder(&data, gapless);
+        decoder.seek_to_sample(sample).unwrap();
+        let (rest, _) = decode_rest(&mut decoder);
+
+        let start = sample as usize * channels;
+        assert!(
+            rest == full[start..],
+            "seeking to sample {} (gapless: {})",
+            sample,
+            gapless
+        );
+    }
+}
+
+#[test]
+#[ignore = "needs minimp3 vectors"]
+fn seek_to_sample() {
+    assert_seeks(false);
+}
+
+#[test]
+#[ignore = "needs minimp3 vectors"]
+fn seek_to_sample_gapless() {
+    assert_seeks(true);
+}